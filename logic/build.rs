@@ -0,0 +1,45 @@
+//! Hashes the source the cached tables of `src/data/cache.rs` are computed by
+//! into `CACHE_SOURCES_HASH`, so that an edit to any of it invalidates the
+//! cache. Whole directories are hashed, so new modules are covered too.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const SOURCES: &[&str] = &["src/data", "src/domain", "src/solve/action.rs", "src/types.rs"];
+
+/// FNV-1a, the same hash the cache uses for its other inputs.
+fn fnv(hash: u64, bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(hash, |h, b| (h ^ *b as u64).wrapping_mul(0x100000001b3))
+}
+
+fn collect(path: &Path, files: &mut Vec<PathBuf>) {
+    match fs::read_dir(path) {
+        Ok(dir) => {
+            for entry in dir.flatten() {
+                collect(&entry.path(), files);
+            }
+        }
+        Err(_) => files.push(path.to_path_buf()),
+    }
+}
+
+fn main() {
+    let mut files = vec![];
+    for source in SOURCES {
+        println!("cargo::rerun-if-changed={source}");
+        collect(Path::new(source), &mut files);
+    }
+    files.sort();
+
+    let mut hash = 0xcbf29ce484222325;
+    for file in files {
+        let contents = fs::read(&file).unwrap_or_else(|e| panic!("Could not read {}: {e}", file.display()));
+        hash = fnv(hash, file.as_os_str().as_encoded_bytes());
+        hash = fnv(hash, &contents);
+    }
+    println!("cargo::rustc-env=CACHE_SOURCES_HASH={hash}");
+}
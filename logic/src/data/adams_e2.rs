@@ -0,0 +1,226 @@
+//! Adams E2 pages with their Adams differentials, as CSV files written by Lin's
+//! program (`AHSS_DATA/*_AdamsE2_ss.csv`), parsed into rows (see
+//! [`AdamsE2::parse`]) and turned into the synthetic comparison data the
+//! truncations converge to (see [`AdamsE2::torsions`]).
+//!
+//! A file has a header naming the columns `stem`, `s`, `base`, `diff` and
+//! `level` (in any order, a leading BOM is ignored) and one row per basis
+//! element of Ext in bidegree (`stem`, `s`). The basis is adapted to the
+//! differentials, so `base` may be a linear combination of Lin's basis elements
+//! (a quoted list like `"0,1"`), and `diff` is the other end of the
+//! differential in the basis of that page, `[NULL]` or empty if there is none.
+//! `level` says what happens to the element:
+//!
+//! - `9000`: a permanent cycle,
+//! - `r < 9000`: hit by a d_r,
+//! - `10000 - r`: supports a d_r.
+//!
+//! A differential hitting a linear combination still kills exactly one basis
+//! element: its target is the one row whose `base` is that combination. So
+//! every source of a d_r has a target row in the bidegree it hits, which
+//! [`AdamsE2::parse`] checks. Elements Lin's program did not pair with another
+//! have a `[NULL]` diff whatever their level; they count as permanent cycles.
+
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::{MAX_STEM, types::Torsion};
+
+/// What happens to a basis element in the Adams spectral sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fate {
+    Permanent,
+    /// Supports a d_r.
+    Source { r: i32 },
+    /// Hit by a d_r.
+    Target { r: i32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdamsE2Row {
+    /// Line in the file, counting from 1 with the header.
+    pub line: usize,
+    pub stem: i32,
+    pub af: i32,
+    /// The basis element, as a sum of Lin's basis elements in its bidegree.
+    pub base: Vec<u32>,
+    /// The other end of its differential, `None` if there is none.
+    pub diff: Option<Vec<u32>>,
+    pub fate: Fate,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AdamsE2 {
+    pub rows: Vec<AdamsE2Row>,
+}
+
+const COLUMNS: [&str; 5] = ["stem", "s", "base", "diff", "level"];
+
+impl AdamsE2 {
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let s = fs::read_to_string(path).map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+        Self::parse(&s).map_err(|e| format!("{}:{e}", path.display()))
+    }
+
+    /// Parse the contents of a CSV. Errors start with the line number.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut lines = s.strip_prefix('\u{feff}').unwrap_or(s).lines().enumerate();
+        let Some((_, header)) = lines.next() else {
+            return Err("1: the file is empty".to_string());
+        };
+        let header = split_row(header).map_err(|e| format!("1: {e}"))?;
+        let mut columns = [0; COLUMNS.len()];
+        for (column, name) in columns.iter_mut().zip(COLUMNS) {
+            *column = header
+                .iter()
+                .position(|h| h.trim() == name)
+                .ok_or_else(|| format!("1: there is no column {name}"))?;
+        }
+
+        let mut e2 = AdamsE2::default();
+        for (i, l) in lines {
+            let line = i + 1;
+            if l.trim().is_empty() {
+                continue;
+            }
+            let row = split_row(l).and_then(|fields| parse_row(&fields, &columns, line));
+            e2.rows.push(row.map_err(|e| format!("{line}: {e}"))?);
+        }
+        e2.check_differentials()?;
+        Ok(e2)
+    }
+
+    /// Every source of a d_r needs a target row one stem down and r
+    /// filtrations up, and every target a source.
+    fn check_differentials(&self) -> Result<(), String> {
+        // (stem, s, r) of the targets -> sources minus targets, first line
+        let mut balance: HashMap<(i32, i32, i32), (i32, usize)> = HashMap::new();
+        for row in self.rows.iter().filter(|r| r.diff.is_some()) {
+            let (key, n) = match row.fate {
+                Fate::Source { r } => ((row.stem - 1, row.af + r, r), 1),
+                Fate::Target { r } => ((row.stem, row.af, r), -1),
+                Fate::Permanent => continue,
+            };
+            balance.entry(key).or_insert((0, row.line)).0 += n;
+        }
+        let unbalanced = balance.into_iter().filter(|(_, (n, _))| *n != 0);
+        match unbalanced.min_by_key(|(_, (_, line))| *line) {
+            None => Ok(()),
+            Some(((stem, af, r), (n, line))) if n > 0 => {
+                Err(format!("{line}: {n} d_{r} into stem {stem}, s {af} have no target there"))
+            }
+            Some(((stem, af, r), (n, line))) => {
+                Err(format!("{line}: {} targets of a d_{r} in stem {stem}, s {af} have no source", -n))
+            }
+        }
+    }
+
+    /// (stem, AF) -> sorted torsions, with stems moved up by `shift` and, if
+    /// `add_one_af`, AFs by one (AHSS generators on a cell have one more AF
+    /// than the Ext class). A permanent cycle is τ-free, the target of a d_r is
+    /// τ^{r-1}-torsion, and a source is only kept (as dead) if `include_zero`.
+    ///
+    /// For every cell in `free_cells` one class in each AF is dropped from its
+    /// stem, as the E1 model does not contain the h_0-tower on a Z summand.
+    pub fn torsions(
+        &self,
+        shift: i32,
+        add_one_af: bool,
+        include_zero: bool,
+        free_cells: &[i32],
+    ) -> HashMap<(i32, i32), Vec<Torsion>> {
+        let mut m: HashMap<(i32, i32), Vec<Torsion>> = HashMap::new();
+        for row in &self.rows {
+            let stem = row.stem + shift;
+            let af = row.af + if add_one_af { 1 } else { 0 };
+            if stem > MAX_STEM {
+                continue;
+            }
+            let torsion = match (row.fate, &row.diff) {
+                (Fate::Permanent, _) | (_, None) => Torsion::default(),
+                (Fate::Target { r }, _) => Torsion::new(r - 1),
+                (Fate::Source { .. }, _) if include_zero => Torsion::zero(),
+                (Fate::Source { .. }, _) => continue,
+            };
+            m.entry((stem, af)).or_default().push(torsion);
+        }
+        for &cell in free_cells {
+            m.retain(|&(stem, _), p| {
+                if stem == cell {
+                    p.pop();
+                }
+                !p.is_empty()
+            });
+        }
+        for j in m.values_mut() {
+            j.sort();
+        }
+        m
+    }
+}
+
+/// Split a CSV row on commas outside double quotes; `""` in a quoted field is
+/// a literal quote.
+fn split_row(l: &str) -> Result<Vec<String>, String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = l.trim_end_matches('\r').chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            c => fields.last_mut().unwrap().push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quote".to_string());
+    }
+    Ok(fields)
+}
+
+fn parse_row(fields: &[String], columns: &[usize; COLUMNS.len()], line: usize) -> Result<AdamsE2Row, String> {
+    let field = |i: usize| {
+        fields
+            .get(columns[i])
+            .map(|f| f.trim())
+            .ok_or_else(|| format!("there is no {} column", COLUMNS[i]))
+    };
+    let int = |i: usize| {
+        let f = field(i)?;
+        f.parse::<i32>().map_err(|_| format!("{} should be a number, found {f:?}", COLUMNS[i]))
+    };
+
+    let level = int(4)?;
+    let fate = match level {
+        9000 => Fate::Permanent,
+        2..9000 => Fate::Target { r: level },
+        9001..=9998 => Fate::Source { r: 10000 - level },
+        _ => return Err(format!("{level} is not a level")),
+    };
+    let base = parse_list(field(2)?).map_err(|e| format!("base {e}"))?;
+    Ok(AdamsE2Row {
+        line,
+        stem: int(0)?,
+        af: int(1)?,
+        base: base.ok_or("base is empty")?,
+        diff: parse_list(field(3)?).map_err(|e| format!("diff {e}"))?,
+        fate,
+    })
+}
+
+/// A list of basis elements like `0`, `0,1` or `[0 1]`; `None` for `[NULL]`
+/// or an empty field.
+fn parse_list(f: &str) -> Result<Option<Vec<u32>>, String> {
+    let f = f.trim_start_matches('[').trim_end_matches(']').trim();
+    if f.is_empty() || f == "NULL" {
+        return Ok(None);
+    }
+    f.split([',', ' '])
+        .filter(|x| !x.is_empty())
+        .map(|x| x.parse::<u32>().map_err(|_| format!("should list basis elements, found {x:?}")))
+        .collect::<Result<_, _>>()
+        .map(Some)
+}
@@ -0,0 +1,97 @@
+//! On-disk cache for the comparison data in [`crate::data::r#static`] and the
+//! algebraic pages in [`crate::data::curtis`]. Building those means running
+//! `compute_pages` over every truncation and parsing all of `AHSS_DATA`, which
+//! dominates the startup of a short session.
+//!
+//! Every table is stored in its own file under `logic/target/cache`, tagged
+//! with a hash of everything it is computed from: the Curtis tables, the
+//! `AHSS_DATA` CSVs, `MAX_STEM`, the crate version, [`CACHE_VERSION`] and the
+//! source of the modules that compute them: every file under `src/data` and
+//! `src/domain`, plus `src/solve/action.rs` and `src/types.rs`, hashed by
+//! `build.rs`. A missing, unreadable or outdated file is rebuilt and rewritten
+//! on first use.
+
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    MAX_STEM,
+    data::{
+        curtis::{CURTIS_TXT, STABLE_CURTIS_TXT},
+        r#static::ahss_data_dir,
+    },
+};
+
+/// Bump this whenever the way the cached tables are computed changes outside
+/// of the hashed source, so that stale caches get rebuilt.
+const CACHE_VERSION: u64 = 1;
+
+/// FNV-1a, as it has to be stable between builds (unlike `DefaultHasher`).
+fn fnv(hash: u64, bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(hash, |h, b| (h ^ *b as u64).wrapping_mul(0x100000001b3))
+}
+
+static INPUT_HASH: LazyLock<u64> = LazyLock::new(|| {
+    let mut hash = 0xcbf29ce484222325;
+    hash = fnv(hash, &CACHE_VERSION.to_le_bytes());
+    hash = fnv(hash, env!("CARGO_PKG_VERSION").as_bytes());
+    hash = fnv(hash, env!("CACHE_SOURCES_HASH").as_bytes());
+    hash = fnv(hash, &MAX_STEM.to_le_bytes());
+    hash = fnv(hash, CURTIS_TXT.as_bytes());
+    hash = fnv(hash, STABLE_CURTIS_TXT.as_bytes());
+
+    let mut files: Vec<_> = fs::read_dir(ahss_data_dir())
+        .map(|dir| dir.filter_map(|e| Some(e.ok()?.path())).collect())
+        .unwrap_or_default();
+    files.sort();
+    for file in files {
+        hash = fnv(hash, file.file_name().unwrap_or_default().as_encoded_bytes());
+        hash = fnv(hash, &fs::read(&file).unwrap_or_default());
+    }
+    hash
+});
+
+fn cache_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("cache")
+        .join(format!("{name}.bin"))
+}
+
+fn load<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let reader = BufReader::new(File::open(path).ok()?);
+    let (hash, value): (u64, T) = bincode::deserialize_from(reader).ok()?;
+    (hash == *INPUT_HASH).then_some(value)
+}
+
+fn store<T: Serialize>(path: &Path, value: &T) -> Result<(), Box<dyn std::error::Error>> {
+    fs::create_dir_all(path.parent().unwrap())?;
+    // Write to a temporary file first, so concurrent runs never see half a cache.
+    let tmp = path.with_extension(format!("tmp{}", std::process::id()));
+    bincode::serialize_into(BufWriter::new(File::create(&tmp)?), &(*INPUT_HASH, value))?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// The table `name`, loaded from the cache if it is up to date and otherwise
+/// computed with `build` (and cached for the next run).
+pub fn cached<T: Serialize + DeserializeOwned>(name: &str, build: impl FnOnce() -> T) -> T {
+    let path = cache_path(name);
+    if let Some(value) = load(&path) {
+        return value;
+    }
+
+    let value = build();
+    if let Err(e) = store(&path, &value) {
+        eprintln!("Could not write cache {}: {e}", path.display());
+    }
+    value
+}
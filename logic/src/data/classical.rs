@@ -0,0 +1,126 @@
+//! The classical homotopy groups of spheres that [`crate::solve::ehp::verify_geometric`]
+//! checks the computed EHP against, loaded at runtime from
+//! `classical_homotopy_groups.json` in the repo root (see [`ClassicalTable::read`]).
+//!
+//! The file is versioned ([`CLASSICAL_TABLE_VERSION`]). Every stem lists the
+//! 2-primary order of π_{n+k}(S^n) for each sphere, with `null` for entries
+//! that are not known, and optionally the 2-primary stable stem. Every known
+//! order is cited through a range of spheres, the stable stem on its own. The table may be extended with more stems or
+//! spheres (e.g. from newer computations) without changing any code.
+
+use std::{collections::BTreeMap, fs, path::{Path, PathBuf}};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{domain::homotopy::HomotopyGroup, io::export::repo_root_path};
+
+/// The version of the file format this build reads.
+pub const CLASSICAL_TABLE_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassicalTable {
+    pub version: u32,
+    #[serde(default)]
+    pub description: String,
+    /// Citation key -> full reference.
+    pub sources: BTreeMap<String, String>,
+    pub stems: Vec<ClassicalStem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassicalStem {
+    pub stem: i32,
+    /// Where `orders` come from, by ranges of spheres.
+    pub sources: Vec<SphereRange>,
+    /// `orders[n - 1]` is the 2-primary order of π_{n+stem}(S^n), a Z summand
+    /// counted as 2. `None` if it is not known.
+    pub orders: Vec<Option<u64>>,
+    /// The 2-primary stable stem, in the [`HomotopyGroup`] notation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stable: Option<String>,
+    /// Citation key for `stable`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stable_source: Option<String>,
+}
+
+/// The spheres `spheres[0]..=spheres[1]` of a stem, cited by `source`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SphereRange {
+    pub spheres: [i32; 2],
+    pub source: String,
+}
+
+impl ClassicalTable {
+    pub fn default_path() -> PathBuf {
+        repo_root_path("classical_homotopy_groups.json")
+    }
+
+    /// Read and validate a table: the version must match, stems must be listed
+    /// in order from 0, orders must be powers of 2, every known order must lie
+    /// in exactly one sphere range, citation keys must be defined in `sources`,
+    /// and stable groups must parse.
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let s = fs::read_to_string(path).map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+        let table: ClassicalTable =
+            serde_json::from_str(&s).map_err(|e| format!("Could not parse {}: {e}", path.display()))?;
+
+        if table.version != CLASSICAL_TABLE_VERSION {
+            return Err(format!(
+                "{} has version {}, expected {CLASSICAL_TABLE_VERSION}",
+                path.display(),
+                table.version
+            ));
+        }
+        for (i, entry) in table.stems.iter().enumerate() {
+            if entry.stem != i as i32 {
+                return Err(format!("Expected stem {i}, found stem {}", entry.stem));
+            }
+            for (n, order) in (1..).zip(&entry.orders) {
+                let Some(order) = order else { continue };
+                if !order.is_power_of_two() {
+                    return Err(format!("Stem {i} has the order {order} on S^{n}, which is not a power of 2"));
+                }
+                let ranges = entry.sources.iter().filter(|r| r.spheres[0] <= n && n <= r.spheres[1]).count();
+                if ranges != 1 {
+                    return Err(format!("Stem {i} cites {ranges} sources for S^{n}, expected one"));
+                }
+            }
+            let range_keys = entry.sources.iter().map(|r| &r.source);
+            for key in range_keys.chain(entry.stable_source.as_ref()) {
+                if !table.sources.contains_key(key) {
+                    return Err(format!("Stem {i} cites the unknown source {key}"));
+                }
+            }
+            if entry.stable.is_some() != entry.stable_source.is_some() {
+                return Err(format!("Stem {i} needs both stable and stable_source, or neither"));
+            }
+            if let Some(stable) = &entry.stable {
+                stable
+                    .parse::<HomotopyGroup>()
+                    .map_err(|e| format!("Stem {i} has an invalid stable group: {e}"))?;
+            }
+        }
+        Ok(table)
+    }
+
+    pub fn max_stem(&self) -> i32 {
+        self.stems.len() as i32 - 1
+    }
+
+    /// The largest sphere any stem has an entry for.
+    pub fn max_sphere(&self) -> i32 {
+        self.stems.iter().map(|s| s.orders.len()).max().unwrap_or(0) as i32
+    }
+
+    /// The order of π_{sphere+stem}(S^sphere), if it is known.
+    pub fn order(&self, sphere: i32, stem: i32) -> Option<u64> {
+        let entry = self.stems.get(stem as usize)?;
+        *entry.orders.get((sphere - 1) as usize)?
+    }
+
+    /// The 2-primary stable stem π_stem^s, if the table has it.
+    pub fn stable_group(&self, stem: i32) -> Option<HomotopyGroup> {
+        let entry = self.stems.get(stem as usize)?;
+        entry.stable.as_ref().map(|g| g.parse().unwrap())
+    }
+}
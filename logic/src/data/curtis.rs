@@ -6,31 +6,36 @@
 //! the crate-wide entry points: the E1 page, the seeded algebraic spectral
 //! sequence, and its computed pages, all initialized lazily on first use.
 
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock};
 
 use crate::{
     MAX_STEM,
+    data::cache::cached,
     domain::{e1::E1, model::SyntheticSS, process::compute_pages, ss::SSPages},
     io::export::Differential,
     types::{Generator, Kind},
 };
 
-static CURTIS_TXT: &str = include_str!("../../../curtis_table.txt");
-static STABLE_CURTIS_TXT: &str = include_str!("../../../curtis_table_stable.txt");
+pub static CURTIS_TXT: &str = include_str!("../../../curtis_table.txt");
+pub static STABLE_CURTIS_TXT: &str = include_str!("../../../curtis_table_stable.txt");
 
 pub static MODEL: LazyLock<E1> = LazyLock::new(|| generate_algebraic_model(false).0);
 
 pub static DATA: LazyLock<SyntheticSS> = LazyLock::new(|| generate_algebraic_model(false).1);
 
-pub static DATA_PAGES: LazyLock<SSPages> =
-    LazyLock::new(|| compute_pages(&DATA, &MODEL, 0, 256, 0, MAX_STEM, true).0);
+pub static DATA_PAGES: LazyLock<SSPages> = LazyLock::new(|| {
+    cached("data_pages", || Arc::unwrap_or_clone(compute_pages(&DATA, &MODEL, 0, 256, 0, MAX_STEM, true).0))
+});
 
 pub static STABLE_MODEL: LazyLock<E1> = LazyLock::new(|| generate_algebraic_model(true).0);
 
 pub static STABLE_DATA: LazyLock<SyntheticSS> = LazyLock::new(|| generate_algebraic_model(true).1);
 
-pub static STABLE_DATA_PAGES: LazyLock<SSPages> =
-    LazyLock::new(|| compute_pages(&STABLE_DATA, &STABLE_MODEL, 0, 256, 0, MAX_STEM, true).0);
+pub static STABLE_DATA_PAGES: LazyLock<SSPages> = LazyLock::new(|| {
+    cached("stable_data_pages", || {
+        Arc::unwrap_or_clone(compute_pages(&STABLE_DATA, &STABLE_MODEL, 0, 256, 0, MAX_STEM, true).0)
+    })
+});
 
 /// A Curtis-table entry that survives (a permanent cycle): a single generator.
 #[derive(Debug, Clone)]
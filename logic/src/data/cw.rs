@@ -0,0 +1,314 @@
+//! CW spectra other than RP^n_m, described in a JSON file (see
+//! [`CwSpectrum::read`]), for which the synthetic AHSS is set up and solved
+//! (see [`crate::solve::automated_cw`]) against an Adams E2 CSV from Lin's
+//! program or, for a stunted CP or HP, Ext from the built-in resolution.
+//!
+//! The E1 page of the AHSS of a spectrum with cells `c_1 < .. < c_k` is a copy
+//! of the synthetic Ext of the sphere on every cell, so it is built from the
+//! generators on the first cell of the stable model: `"tag[1]"` becomes
+//! `"tag[c]"` for every cell `c`, with the E1 torsion the AHSS log gives it.
+//! How the cells are attached only enters through the differentials: the
+//! algebraic ones (`differentials`, e.g. the d_1's given by h_0 for a degree 2
+//! attaching map) and any further facts (`facts`, in the format of the logs).
+//!
+//! The file is versioned ([`CW_SPECTRUM_VERSION`]). Unlike for RP^n_m there is
+//! no James periodicity, so every algebraic differential has to be listed,
+//! except for a two cell projective space ([`CwSpectrum::algebraic`]). Longer
+//! stunted CP and HP, which would need their periodicities, are not supported
+//! yet. The
+//! other facts are found by the solver; only those it can not decide from the
+//! Adams E2 data need to be listed.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    MAX_STEM, MAX_VERIFY_STEM,
+    data::{
+        adams_e2::AdamsE2, curtis::STABLE_MODEL, naming::name_get_tag, resolution::Resolution,
+        steenrod::ProjectiveModule,
+    },
+    domain::{e1::E1, model::SyntheticSS},
+    solve::action::{Action, process_action},
+    types::{Generator, Kind, Torsion},
+};
+
+/// The version of the file format this build reads.
+pub const CW_SPECTRUM_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CwSpectrum {
+    pub version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Dimensions of the cells, at most one per dimension.
+    pub cells: Vec<i32>,
+    /// Cells attached to the cell one below by a map of degree 2. Their
+    /// h_0-tower is carried onto the one below minus its unit by d_1's the E1
+    /// model does not contain (it has no h_0-towers), so they have no unit
+    /// `"[c]"`. On RP^n_m these are the even cells.
+    #[serde(default)]
+    pub h0_cells: Vec<i32>,
+    /// Cells carrying a Z summand in homology, whose h_0-tower is dropped from
+    /// the Adams E2 data as the E1 model does not contain it, except the unit
+    /// `"[c]"`, which the model has.
+    #[serde(default)]
+    pub free_cells: Vec<i32>,
+    /// Adams E2 CSV of the spectrum, relative to the description file. With
+    /// `projective` it is optional and only supplies the Adams differentials.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adams_e2: Option<PathBuf>,
+    /// The cohomology of the spectrum, if it is a stunted projective space:
+    /// its Ext is then computed by a minimal resolution, and for two cells so
+    /// are the algebraic differentials (see [`CwSpectrum::algebraic`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub projective: Option<ProjectiveModule>,
+    /// Added to the stems of `adams_e2`, if it was computed for a suspension.
+    #[serde(default)]
+    pub adams_e2_shift: i32,
+    /// Only check up to this stem, e.g. as far as the facts are known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_stem: Option<i32>,
+    /// The algebraic differentials of the AHSS, besides those computed from
+    /// `projective`.
+    #[serde(default)]
+    pub differentials: Vec<CwDifferential>,
+    /// Further differentials and tau multiplications, as in the logs, that
+    /// the solver starts from. Only `AddDiff`, `AddInt` and `AddExt` are
+    /// allowed.
+    #[serde(default)]
+    pub facts: Vec<Action>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CwDifferential {
+    pub from: String,
+    pub to: String,
+}
+
+impl CwSpectrum {
+    /// Read and validate a description: the version must match, cells must be
+    /// strictly increasing, free cells must be cells, `h0_cells` must have a
+    /// cell one below, facts must be differentials or tau multiplications and
+    /// there must be an Adams E2 CSV or a projective space with these cells.
+    /// Names are only checked by [`CwSpectrum::model`].
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let s = fs::read_to_string(path).map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+        let mut spectrum: CwSpectrum =
+            serde_json::from_str(&s).map_err(|e| format!("Could not parse {}: {e}", path.display()))?;
+
+        if spectrum.version != CW_SPECTRUM_VERSION {
+            return Err(format!(
+                "{} has version {}, expected {CW_SPECTRUM_VERSION}",
+                path.display(),
+                spectrum.version
+            ));
+        }
+        if spectrum.cells.is_empty() {
+            return Err(format!("{} has no cells", spectrum.name));
+        }
+        if !spectrum.cells.is_sorted_by(|a, b| a < b) || spectrum.cells[0] < 0 {
+            return Err(format!(
+                "The cells of {} must be non-negative and strictly increasing",
+                spectrum.name
+            ));
+        }
+        if let Some(cell) = spectrum.free_cells.iter().find(|c| !spectrum.cells.contains(c)) {
+            return Err(format!("The free cell {cell} of {} is not a cell", spectrum.name));
+        }
+        if let Some(cell) = spectrum.h0_cells.iter().find(|c| !spectrum.cells.contains(&(*c - 1))) {
+            return Err(format!("{} has no cell below the h_0 cell {cell}", spectrum.name));
+        }
+        if let Some(fact) = spectrum.facts.iter().find(|a| {
+            !matches!(a, Action::AddDiff { .. } | Action::AddInt { .. } | Action::AddExt { .. })
+        }) {
+            return Err(format!("{fact:?} can not be used as a fact about a CW spectrum"));
+        }
+        match spectrum.projective {
+            Some(module) if (module.bot..=module.top).filter(|&n| module.dim(n) == 1).ne(spectrum.cells.iter().copied()) => {
+                return Err(format!("The cells of {} are not those of {module:?}", spectrum.name));
+            }
+            None if spectrum.adams_e2.is_none() => {
+                return Err(format!("{} has neither an Adams E2 CSV nor a projective space", spectrum.name));
+            }
+            _ => {}
+        }
+
+        if let (Some(dir), Some(adams_e2)) = (path.parent(), &mut spectrum.adams_e2) {
+            *adams_e2 = dir.join(&adams_e2);
+        }
+        Ok(spectrum)
+    }
+
+    pub fn bot_cell(&self) -> i32 {
+        self.cells[0]
+    }
+
+    pub fn top_cell(&self) -> i32 {
+        self.cells[self.cells.len() - 1]
+    }
+
+    /// The last stem to check: at most `max_stem`, and the E1 page has to be
+    /// complete, where the Curtis data ends one stem below [`MAX_STEM`] on the
+    /// first cell and a stem is only checked together with the one above it.
+    pub fn max_stem(&self) -> i32 {
+        let complete = MAX_VERIFY_STEM.min(self.bot_cell() + MAX_VERIFY_STEM - 1);
+        self.max_stem.map_or(complete, |s| s.min(complete))
+    }
+
+    /// The E1 page and the algebraic and listed facts. The E1 torsion of a
+    /// class on any cell is that of the class on the first cell in `stable`,
+    /// the replayed AHSS of RP^∞.
+    pub fn model(&self, stable: &SyntheticSS) -> Result<(E1, SyntheticSS), String> {
+        let (differentials, dropped) = self.algebraic()?;
+        let mut generators = vec![];
+        let mut torsions = HashMap::new();
+        for (id, g) in STABLE_MODEL.enumerate().filter(|(_, g)| g.y == 1) {
+            for &cell in &self.cells {
+                if g.name == "[1]" && self.h0_cells.contains(&cell) {
+                    continue;
+                }
+                let stem = g.stem - 1 + cell;
+                let name = format!("{}[{cell}]", name_get_tag(&g.name));
+                if stem <= MAX_STEM && !dropped.contains(&name) {
+                    torsions.insert(name.clone(), stable.generators()[id]);
+                    generators.push(Generator::new(name, stem, cell, g.af, cell + 1, None));
+                }
+            }
+        }
+
+        generators.sort_by_key(|x| x.af);
+        generators.sort_by_key(|x| x.y);
+        generators.sort_by_key(|x| x.stem);
+        let mut model = E1::new(generators);
+
+        // Both ends of an algebraic differential die on the sphere of its source.
+        for d in &differentials {
+            let from = model.resolve(&d.from).map_err(|e| e.to_string())?;
+            let to = model.resolve(&d.to).map_err(|e| e.to_string())?;
+            let dies = Some(model.y(from) + 1);
+            model.get_mut(from).dies = dies;
+            model.get_mut(to).dies = dies;
+        }
+
+        let mut data = SyntheticSS::empty(model.clone());
+        for (id, g) in model.enumerate() {
+            data.set_torsion(id, torsions[&g.name]);
+        }
+
+        let algebraic = differentials.iter().map(|d| Action::AddDiff {
+            from: d.from.clone(),
+            to: d.to.clone(),
+            kind: Kind::Algebraic,
+            proof: None,
+        });
+        for mut action in algebraic.chain(self.facts.iter().cloned()) {
+            action.resolve_names(&model).map_err(|e| e.to_string())?;
+            if let Action::AddDiff { from, to, kind: Kind::Algebraic, .. } = &action
+                && model.get_name(to).af != model.get_name(from).af + 1
+            {
+                return Err(format!("The algebraic differential {from} -> {to} does not raise AF by one"));
+            }
+            process_action(&mut data, &model, &action, false)
+                .map_err(|_| format!("{action:?} is not a valid fact about {}", self.name))?;
+        }
+        Ok((model, data))
+    }
+
+    /// The algebraic differentials, and the classes left out of the E1 page.
+    ///
+    /// Besides the listed ones, a two cell projective space has those of its
+    /// attaching map, detected by h_i: x on the top cell hits h_i x on the
+    /// bottom one. They are read off the resolution of the sphere, whose Ext
+    /// is matched with the generators on the first cell of the stable model by
+    /// bidegree, so x and h_i x have to be alone in theirs up to
+    /// [`CwSpectrum::max_stem`]. The h_0-multiples of the unit are not in the
+    /// model, so the h_0^k h_i they hit are left out instead.
+    pub fn algebraic(&self) -> Result<(Vec<CwDifferential>, Vec<String>), String> {
+        let mut differentials = self.differentials.clone();
+        let mut dropped = vec![];
+        let Some(module) = self.projective.filter(|_| self.cells.len() == 2) else {
+            return Ok((differentials, dropped));
+        };
+        let (bot, top) = (self.bot_cell(), self.top_cell());
+        let length = top - bot;
+        if !module.acts(&[length as u32], bot) {
+            return Ok((differentials, dropped));
+        }
+        if length.count_ones() != 1 || length == 1 {
+            return Err(format!(
+                "The attaching map of {} is not detected by an h_i with i > 0; list its differentials",
+                self.name
+            ));
+        }
+        let i = length.trailing_zeros();
+
+        let mut tags: HashMap<(i32, i32), Vec<&str>> = HashMap::new();
+        for (_, g) in STABLE_MODEL.enumerate().filter(|(_, g)| g.y == 1) {
+            tags.entry((g.stem - 1, g.af - 1)).or_default().push(name_get_tag(&g.name));
+        }
+        let max_stem = self.max_stem() - bot;
+        let max_s = max_stem / 2 + 4;
+        let resolution = Resolution::new(ProjectiveModule::rp(0, 0), max_stem, max_s);
+        let ext = resolution.ext();
+        let undetermined = |stem| format!("h_{i} times stem {stem} of {} is not determined by bidegree", self.name);
+        // Differentials from the stem above the last one count there too.
+        for (&(stem, s), &n) in ext.iter().sorted() {
+            if stem + top > self.max_stem() + 1 {
+                continue;
+            }
+            let target = (stem + length - 1, s + 1);
+            let Some(products) = resolution.h_product(i, stem, s) else {
+                continue;
+            };
+            if products.iter().all(|p| p.is_empty()) {
+                continue;
+            }
+            let to = match tags.get(&target).map(Vec::as_slice) {
+                Some([to]) if ext.get(&target) == Some(&1) => format!("{to}[{bot}]"),
+                _ => return Err(undetermined(stem + top)),
+            };
+            match tags.get(&(stem, s)).map(Vec::as_slice) {
+                Some([from]) if n == 1 => differentials.push(CwDifferential { from: format!("{from}[{top}]"), to }),
+                None if stem == 0 => dropped.push(to),
+                _ => return Err(undetermined(stem + top)),
+            }
+        }
+        Ok((differentials, dropped))
+    }
+
+    /// The Adams E2 data to compare the AHSS with, in the AF of the model:
+    /// Lin's, or Ext from the resolution of `projective`, τ-free unless there
+    /// is a CSV for the Adams differentials.
+    pub fn adams_e2(&self) -> Result<HashMap<(i32, i32), Vec<Torsion>>, String> {
+        let e2 = self.adams_e2.as_deref().map(AdamsE2::read).transpose()?;
+        let mut m = match (self.projective, e2) {
+            (Some(module), e2) => {
+                let max_stem = self.max_stem();
+                let resolution = Resolution::new(module, max_stem, max_stem / 2 + 4);
+                resolution.compare_data(true, e2.as_ref().map(|e2| (e2, self.adams_e2_shift)), &[])?
+            }
+            (None, Some(e2)) => e2.torsions(self.adams_e2_shift, true, false, &[]),
+            (None, None) => return Err(format!("{} has neither an Adams E2 CSV nor a projective space", self.name)),
+        };
+        for &cell in &self.free_cells {
+            m.retain(|&(stem, af), p| {
+                if stem == cell
+                    && af > 1
+                    && let Some(i) = p.iter().position(|t| *t == Torsion::default())
+                {
+                    p.remove(i);
+                }
+                !p.is_empty()
+            });
+        }
+        Ok(m)
+    }
+}
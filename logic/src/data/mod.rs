@@ -1,10 +1,31 @@
 //! Static input data and the parsers that turn it into domain objects.
 //!
+//! - [`adams_e2`]: parser for the Adams E2 CSVs in `AHSS_DATA`.
+//! - [`cache`]: on-disk cache for the tables below that are expensive to build.
+//! - [`classical`]: the classical homotopy groups of spheres, read from a
+//!   versioned data file, that the EHP is checked against.
+//! - [`cw`]: CW spectra described in a versioned data file, whose AHSS is
+//!   built from the stable model and solved against an Adams E2 CSV.
 //! - [`curtis`]: parses the Curtis tables into the algebraic E1 model and the
 //!   lazily-initialized `MODEL`/`DATA` statics used throughout the crate.
+//! - [`names`]: Ext and Toda names of classes, read from a versioned data file.
 //! - [`naming`]: string helpers for the generator naming scheme (`"tag[sphere]"`).
+//! - [`resolution`]: a minimal resolution over the Steenrod algebra, giving
+//!   Ext of the sphere and of any stunted RP, CP or HP without Lin's program.
+//! - [`steenrod`]: the Steenrod algebra in the Milnor basis and the cohomology
+//!   of stunted projective spaces.
+//! - [`truncations`]: which RP truncations are checked, discovered from the
+//!   `AHSS_DATA` CSVs plus a versioned config.
 //! - [`r#static`]: comparison data loaded from CSV plus assorted lookup tables.
 
 pub mod r#static;
+pub mod adams_e2;
+pub mod cache;
+pub mod classical;
 pub mod curtis;
+pub mod cw;
+pub mod names;
 pub mod naming;
+pub mod resolution;
+pub mod steenrod;
+pub mod truncations;
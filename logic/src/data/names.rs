@@ -0,0 +1,236 @@
+//! Ext (Lin/Bruner style, e.g. `h_1`, `c_0`, `Ph_1`, `d_0`) and Toda (e.g. `η`,
+//! `ε`, `μ`, `κ`) names of classes, loaded at runtime from `class_names.json`
+//! in the repo root (see [`Names::read`]).
+//!
+//! Names are keyed by the leading lambda word of a class in the Curtis table,
+//! which for a generator `"tag[n]"` is [`generating_tag`] (`"n tag"`). The
+//! Ext name of a generator is that of its tag on the cell `n`, e.g.
+//! `"2 3 3[6]"` is `c_0[6]`; its Toda name is that of the element its full
+//! word detects, for the EHP on the sphere `S^{n+1}` it is born on.
+//!
+//! The file is versioned ([`NAMES_VERSION`]) and may be extended without
+//! changing any code; classes without an entry simply get no name.
+
+use std::{collections::HashMap, fs, path::{Path, PathBuf}, sync::LazyLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::naming::{generated_by_name, generating_tag, name_get_tag, name_to_sphere},
+    io::export::repo_root_path,
+};
+
+/// The version of the file format this build reads.
+pub const NAMES_VERSION: u32 = 1;
+
+/// The names from [`Names::default_path`]. If the file can not be read this
+/// is empty, so nothing gets named.
+pub static NAMES: LazyLock<Names> = LazyLock::new(|| {
+    Names::read(&Names::default_path()).unwrap_or_else(|e| {
+        eprintln!("{e}, classes will not be named");
+        Names::default()
+    })
+});
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct NamesFile {
+    version: u32,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    sources: HashMap<String, String>,
+    classes: Vec<ClassName>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassName {
+    /// Leading lambda word in the Curtis table, e.g. `"2 3 3"`.
+    pub word: String,
+    /// Name in Ext_A(F_2, F_2), e.g. `"c_0"`.
+    pub ext: String,
+    /// Stable homotopy class it detects, if it is a permanent cycle, e.g. `"ε"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toda: Option<String>,
+    /// Toda's name on the sphere it is born on in the EHP, e.g. `"ε_3"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toda_unstable: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Names {
+    by_word: HashMap<String, ClassName>,
+    word_by_ext: HashMap<String, String>,
+    word_by_toda: HashMap<String, String>,
+}
+
+impl Names {
+    pub fn default_path() -> PathBuf {
+        repo_root_path("class_names.json")
+    }
+
+    /// Read and validate a names file: the version must match, and words, Ext
+    /// names and Toda names must each be unique.
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let s = fs::read_to_string(path).map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+        let file: NamesFile =
+            serde_json::from_str(&s).map_err(|e| format!("Could not parse {}: {e}", path.display()))?;
+
+        if file.version != NAMES_VERSION {
+            return Err(format!(
+                "{} has version {}, expected {NAMES_VERSION}",
+                path.display(),
+                file.version
+            ));
+        }
+
+        let mut names = Names::default();
+        for class in file.classes {
+            if names.word_by_ext.insert(class.ext.clone(), class.word.clone()).is_some() {
+                return Err(format!("The Ext name {} is used twice", class.ext));
+            }
+            for toda in [&class.toda, &class.toda_unstable].into_iter().flatten() {
+                if names.word_by_toda.insert(toda.clone(), class.word.clone()).is_some() {
+                    return Err(format!("The Toda name {toda} is used twice"));
+                }
+            }
+            if let Some(old) = names.by_word.insert(class.word.clone(), class) {
+                return Err(format!("The word {} is named twice", old.word));
+            }
+        }
+        Ok(names)
+    }
+
+    /// The Ext name of a lambda word, empty for the empty word (the cell
+    /// itself), so that `"[n]"` keeps its name.
+    pub fn ext(&self, word: &str) -> Option<&str> {
+        if word.is_empty() {
+            return Some("");
+        }
+        self.by_word.get(word).map(|c| c.ext.as_str())
+    }
+
+    /// The lambda word with the given Ext name, the inverse of [`Names::ext`].
+    pub fn word(&self, ext: &str) -> Option<&str> {
+        if ext.is_empty() {
+            return Some("");
+        }
+        self.word_by_ext.get(ext).map(|w| w.as_str())
+    }
+
+    /// The Ext name of generator `name`, e.g. `"2 3 3[6]"` -> `"c_0[6]"`.
+    pub fn alg_name(&self, name: &str) -> Option<String> {
+        let ext = self.ext(name_get_tag(name))?;
+        Some(format!("{ext}[{}]", name_to_sphere(name)))
+    }
+
+    /// The Toda name of the element generator `name` detects, if there is one.
+    /// For the EHP (`stable == false`) this prefers Toda's name on the sphere
+    /// `S^{n+1}` the generator is born on.
+    pub fn hom_name(&self, name: &str, stable: bool) -> Option<String> {
+        let class = self.by_word.get(&generating_tag(name))?;
+        let unstable = if stable { None } else { class.toda_unstable.as_ref() };
+        unstable.or(class.toda.as_ref()).cloned()
+    }
+
+    /// Canonical generator names `input` could be a spelling of, most literal
+    /// first. Not all of them need to exist; see [`crate::domain::e1::E1::resolve`].
+    ///
+    /// Accepted are lambda words with stray whitespace (`"5  3 [6]"`), full
+    /// leading words without a sphere (`"6 5 3"` for `"5 3[6]"`), Ext names of
+    /// the tag on a cell (`"h_1 d_0[5]"`), Ext and Toda names of a whole class
+    /// (`"c_0"`, `"ε"`, `"ε_3"`) and Toda's `x_n` for `x` on `S^n`, from the
+    /// sphere it is born on (`"σ_9"`). Ext and Toda names may be spelled with
+    /// Unicode sub/superscripts, without underscores (`"h1d0"`, `"σ²"`) and with
+    /// Greek letters written out (`"sigma^2"`, `"nubar"`).
+    pub fn candidates(&self, input: &str) -> Vec<String> {
+        let input = input.trim();
+        let mut out = vec![input.to_string()];
+
+        let (head, sphere) = match input.split_once('[') {
+            Some((head, rest)) => match rest.trim_end().strip_suffix(']').map(|n| n.trim().parse::<i32>()) {
+                Some(Ok(n)) => (head, Some(n)),
+                _ => return out,
+            },
+            None => (input, None),
+        };
+
+        if head.chars().all(|c| c.is_ascii_digit() || c.is_whitespace()) {
+            let word = head.split_whitespace().collect::<Vec<_>>().join(" ");
+            match sphere {
+                Some(n) => out.push(format!("{word}[{n}]")),
+                None if !word.is_empty() => out.push(generated_by_name(&word)),
+                None => {}
+            }
+        }
+
+        let name = normalize(head);
+        match sphere {
+            Some(n) => out.extend(self.word(&name).map(|w| format!("{w}[{n}]"))),
+            None => {
+                for word in [self.word(&name), self.word_by_toda.get(&name).map(|w| w.as_str())].into_iter().flatten() {
+                    if !word.is_empty() {
+                        out.push(generated_by_name(word));
+                    }
+                }
+                if let Some((base, n)) = name.rsplit_once('_')
+                    && let Ok(n) = n.parse::<i32>()
+                    && let Some(word) = self.word_by_toda.get(base)
+                    && n > generated_by_sphere(word)
+                {
+                    out.push(generated_by_name(word));
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Greek letters in Toda's names, spelled out. Longest first, as `eta` is in
+/// `zeta`.
+const GREEK: [(&str, &str); 8] = [
+    ("epsilon", "ε"),
+    ("kappa", "κ"),
+    ("sigma", "σ"),
+    ("zeta", "ζ"),
+    ("eta", "η"),
+    ("rho", "ρ"),
+    ("mu", "μ"),
+    ("nu", "ν"),
+];
+
+/// Bring an Ext or Toda name into the spelling of `class_names.json`: no
+/// whitespace, `^`/`_` instead of Unicode super/subscripts, an underscore
+/// between a lowercase letter and its index (`h1` -> `h_1`), Greek letters and
+/// `bar` as symbols.
+fn normalize(name: &str) -> String {
+    let mut s: String = name.chars().filter(|c| !c.is_whitespace()).collect();
+    for (latin, greek) in GREEK {
+        s = s.replace(latin, greek);
+    }
+    s = s.replace("bar", "\u{304}");
+
+    let mut out = String::new();
+    let mut prev = None;
+    for c in s.chars() {
+        if let Some(d) = "⁰¹²³⁴⁵⁶⁷⁸⁹".chars().position(|x| x == c) {
+            out.push('^');
+            out.push(char::from_digit(d as u32, 10).unwrap());
+        } else if let Some(d) = "₀₁₂₃₄₅₆₇₈₉".chars().position(|x| x == c) {
+            out.push('_');
+            out.push(char::from_digit(d as u32, 10).unwrap());
+        } else {
+            if c.is_ascii_digit() && prev.is_some_and(|p: char| p.is_lowercase() || p == '\u{304}') {
+                out.push('_');
+            }
+            out.push(c);
+        }
+        prev = Some(c);
+    }
+    out
+}
+
+/// The sphere the EHP generator with leading word `word` is born on minus
+/// one, i.e. its first number.
+fn generated_by_sphere(word: &str) -> i32 {
+    word.split_whitespace().next().and_then(|n| n.parse().ok()).unwrap_or(0)
+}
@@ -0,0 +1,311 @@
+//! A minimal resolution of the cohomology of a stunted projective space (the
+//! sphere for RP_0^0, see [`ProjectiveModule`]) over the mod 2 Steenrod
+//! algebra, so that Ext and the synthetic comparison data of any truncation
+//! can be computed without Lin's program (see [`Resolution`]).
+//!
+//! The resolution is built degree by degree: in internal degree t, for each
+//! s, the new generators of C_s span a complement of the image of d_s in the
+//! kernel of d_{s-1} (of the augmentation C_0 -> H^* for s = 0). Their number
+//! is the dimension of Ext^{s,t}. The linear algebra is over F_2 on bit
+//! vectors, which is quick enough for the stems the Curtis tables cover.
+//!
+//! Ext alone is the E2 page; the Adams differentials that turn it into
+//! synthetic data can be taken from an [`AdamsE2`] table (see
+//! [`Resolution::compare_data`]).
+
+use std::collections::HashMap;
+
+use crate::{
+    data::{
+        adams_e2::{AdamsE2, Fate},
+        steenrod::{Milnor, MilnorBasis, ProjectiveModule, degree, multiply},
+    },
+    types::Torsion,
+};
+
+/// A vector over F_2.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BitVec(Vec<u64>);
+
+impl BitVec {
+    fn zero(len: usize) -> Self {
+        BitVec(vec![0; len.div_ceil(64)])
+    }
+
+    fn get(&self, i: usize) -> bool {
+        self.0[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    fn flip(&mut self, i: usize) {
+        self.0[i / 64] ^= 1 << (i % 64);
+    }
+
+    fn add(&mut self, other: &BitVec) {
+        for (a, b) in self.0.iter_mut().zip(&other.0) {
+            *a ^= b;
+        }
+    }
+
+    fn first_one(&self) -> Option<usize> {
+        self.0.iter().enumerate().find(|(_, w)| **w != 0).map(|(i, w)| i * 64 + w.trailing_zeros() as usize)
+    }
+
+    fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.iter().enumerate().flat_map(|(i, &w)| (0..64).filter(move |b| w >> b & 1 == 1).map(move |b| i * 64 + b))
+    }
+}
+
+/// Row echelon form of a set of vectors, for testing membership in their span.
+#[derive(Debug, Default)]
+struct Echelon {
+    rows: Vec<(usize, BitVec)>,
+}
+
+impl Echelon {
+    /// Reduce `v` by the rows; if it is not in their span, add it and return true.
+    fn insert(&mut self, mut v: BitVec) -> bool {
+        for (pivot, row) in &self.rows {
+            if v.get(*pivot) {
+                v.add(row);
+            }
+        }
+        match v.first_one() {
+            Some(pivot) => {
+                self.rows.push((pivot, v));
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// A generator of C_s: its degree and its image, a sum of Milnor basis
+/// elements on generators of C_{s-1} (on classes x^n of the module for s = 0).
+#[derive(Debug, Clone)]
+struct Generator {
+    degree: i32,
+    image: Vec<(usize, Milnor)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Resolution {
+    pub module: ProjectiveModule,
+    pub max_stem: i32,
+    /// `generators[s]`, in order of degree.
+    generators: Vec<Vec<Generator>>,
+}
+
+impl Resolution {
+    /// Resolve `module` far enough to know Ext^{s,t} for all `s <= max_s`
+    /// and stems `t - s <= max_stem`.
+    pub fn new(module: ProjectiveModule, max_stem: i32, max_s: i32) -> Self {
+        let max_t = max_stem + max_s;
+        let basis = MilnorBasis::new(max_t - module.bot.min(0));
+        let mut resolution = Resolution {
+            module,
+            max_stem,
+            generators: vec![vec![]; (max_s + 1) as usize],
+        };
+
+        for t in module.bot..=max_t {
+            // The kernel of the previous map in degree t, starting with all of
+            // the module for the augmentation.
+            let mut kernel: Vec<BitVec> = (0..module.dim(t))
+                .map(|_| {
+                    let mut v = BitVec::zero(1);
+                    v.flip(0);
+                    v
+                })
+                .collect();
+            for s in 0..=max_s as usize {
+                let target = if s == 0 { vec![(0, 0); module.dim(t)] } else { resolution.layout(&basis, s - 1, t) };
+                let index: HashMap<(usize, usize), usize> = target.iter().enumerate().map(|(i, &x)| (x, i)).collect();
+
+                // New generators for what the image misses of the kernel.
+                let mut span = Echelon::default();
+                for v in resolution.images(&basis, s, t, &index) {
+                    span.insert(v);
+                }
+                for v in kernel {
+                    if !span.insert(v.clone()) {
+                        continue;
+                    }
+                    let image = match s {
+                        0 => vec![],
+                        _ => v
+                            .ones()
+                            .map(|i| {
+                                let (h, b) = target[i];
+                                (h, basis.in_degree(t - resolution.degree(s - 1, h))[b].clone())
+                            })
+                            .collect(),
+                    };
+                    resolution.generators[s].push(Generator { degree: t, image });
+                }
+
+                if s == max_s as usize {
+                    break;
+                }
+                // The kernel of d_s in degree t, now with the new generators.
+                kernel = kernel_of(&resolution.images(&basis, s, t, &index));
+            }
+        }
+        resolution
+    }
+
+    fn degree(&self, s: usize, g: usize) -> i32 {
+        self.generators[s][g].degree
+    }
+
+    /// The basis of C_s in degree t: (generator, index in the Milnor basis).
+    fn layout(&self, basis: &MilnorBasis, s: usize, t: i32) -> Vec<(usize, usize)> {
+        self.generators[s]
+            .iter()
+            .enumerate()
+            .flat_map(|(g, generator)| (0..basis.in_degree(t - generator.degree).len()).map(move |a| (g, a)))
+            .collect()
+    }
+
+    /// d of the basis of C_s in degree t, in the basis of C_{s-1} (of the
+    /// module for s = 0) given by `index`.
+    fn images(&self, basis: &MilnorBasis, s: usize, t: i32, index: &HashMap<(usize, usize), usize>) -> Vec<BitVec> {
+        let mut images = vec![];
+        for generator in &self.generators[s] {
+            for a in basis.in_degree(t - generator.degree) {
+                let mut v = BitVec::zero(index.len());
+                if s == 0 {
+                    if self.module.acts(a, generator.degree) {
+                        v.flip(0);
+                    }
+                } else {
+                    for (h, b) in &generator.image {
+                        for c in multiply(a, b) {
+                            debug_assert_eq!(degree(&c) + self.degree(s - 1, *h), t);
+                            v.flip(index[&(*h, basis.index(&c))]);
+                        }
+                    }
+                }
+                images.push(v);
+            }
+        }
+        images
+    }
+
+    /// dim Ext^{s,t}, indexed by (stem, s).
+    pub fn ext(&self) -> HashMap<(i32, i32), usize> {
+        let mut m = HashMap::new();
+        for (s, gens) in self.generators.iter().enumerate() {
+            for g in gens {
+                let stem = g.degree - s as i32;
+                if stem <= self.max_stem {
+                    *m.entry((stem, s as i32)).or_insert(0) += 1;
+                }
+            }
+        }
+        m
+    }
+
+    /// Multiplication by h_i on Ext^s in `stem`, or `None` if s + 1 is beyond
+    /// the resolution. The classes of a bidegree are numbered in the order of
+    /// their generators; entry `x` lists the classes of Ext^{s+1} in stem
+    /// `stem + 2^i - 1` whose sum is h_i times class `x`.
+    ///
+    /// In a minimal resolution this is read off the differential: h_i x has
+    /// class `y` as a summand iff d(y) has Sq(2^i) on the generator of `x`.
+    pub fn h_product(&self, i: u32, stem: i32, s: i32) -> Option<Vec<Vec<usize>>> {
+        let s = s as usize;
+        if s + 1 >= self.generators.len() {
+            return None;
+        }
+        let t = stem + s as i32;
+        let sources: Vec<usize> = (0..self.generators[s].len()).filter(|&g| self.degree(s, g) == t).collect();
+        let sq = vec![1 << i];
+        let mut products = vec![vec![]; sources.len()];
+        let targets = self.generators[s + 1].iter().filter(|g| g.degree == t + (1 << i));
+        for (y, target) in targets.enumerate() {
+            for (h, a) in &target.image {
+                if *a == sq
+                    && let Some(x) = sources.iter().position(|g| g == h)
+                {
+                    products[x].push(y);
+                }
+            }
+        }
+        Some(products)
+    }
+
+    /// Synthetic comparison data like [`AdamsE2::torsions`]: Ext with AF
+    /// `s + 1` if `add_one_af`, τ-free unless `differentials` (a table of
+    /// the same spectrum, its stems moved up by `shift`) says a class is hit
+    /// by a d_r (τ^{r-1}-torsion) or supports one (dropped). `free_cells` lose
+    /// one class per AF as there. The h_0-towers stop at `max_s`.
+    ///
+    /// Fails if the table has more differentials in a bidegree than Ext has
+    /// classes, i.e. it is not a table of this spectrum.
+    pub fn compare_data(
+        &self,
+        add_one_af: bool,
+        differentials: Option<(&AdamsE2, i32)>,
+        free_cells: &[i32],
+    ) -> Result<HashMap<(i32, i32), Vec<Torsion>>, String> {
+        let mut fates: HashMap<(i32, i32), Vec<Fate>> = HashMap::new();
+        if let Some((e2, shift)) = differentials {
+            for row in &e2.rows {
+                if row.diff.is_some() && row.fate != Fate::Permanent {
+                    fates.entry((row.stem + shift, row.af)).or_default().push(row.fate);
+                }
+            }
+        }
+
+        let mut m: HashMap<(i32, i32), Vec<Torsion>> = HashMap::new();
+        for (&(stem, s), &n) in &self.ext() {
+            let fates = fates.remove(&(stem, s)).unwrap_or_default();
+            if fates.len() > n {
+                return Err(format!("The table has {} differentials in stem {stem}, s {s} but Ext has dimension {n}", fates.len()));
+            }
+            let af = s + if add_one_af { 1 } else { 0 };
+            let torsions = m.entry((stem, af)).or_default();
+            torsions.extend(fates.iter().filter_map(|f| match f {
+                Fate::Target { r } => Some(Torsion::new(r - 1)),
+                _ => None,
+            }));
+            torsions.extend((fates.len()..n).map(|_| Torsion::default()));
+        }
+        for &cell in free_cells {
+            m.retain(|&(stem, _), p| {
+                if stem == cell {
+                    p.pop();
+                }
+                !p.is_empty()
+            });
+        }
+        m.retain(|_, p| !p.is_empty());
+        for j in m.values_mut() {
+            j.sort();
+        }
+        Ok(m)
+    }
+}
+
+/// A basis of the vectors whose combination of `rows` vanishes, as vectors
+/// over the rows.
+fn kernel_of(rows: &[BitVec]) -> Vec<BitVec> {
+    let mut reduced: Vec<(usize, BitVec, BitVec)> = vec![];
+    let mut kernel = vec![];
+    for (i, row) in rows.iter().enumerate() {
+        let mut v = row.clone();
+        let mut combination = BitVec::zero(rows.len());
+        combination.flip(i);
+        for (pivot, r, c) in &reduced {
+            if v.get(*pivot) {
+                v.add(r);
+                combination.add(c);
+            }
+        }
+        match v.first_one() {
+            Some(pivot) => reduced.push((pivot, v, combination)),
+            None => kernel.push(combination),
+        }
+    }
+    kernel
+}
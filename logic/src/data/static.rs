@@ -1,23 +1,29 @@
 //! Static lookup tables and externally-computed comparison data.
 //!
-//! This holds the truncation lists that drive the RP^n / sphere computations,
+//! This holds the truncation lists that drive the RP^n / sphere computations
+//! (built from the CSVs present and `data::truncations`),
 //! the EHP<->AHSS generator index maps, and the Adams E2 spectral-sequence data
 //! read from the `AHSS_DATA` CSV files (used to check our results against Lin's
 //! program). Everything is `LazyLock` so it is only built when first accessed.
 
 use std::{
     collections::HashMap,
-    fs::File,
-    io::{BufRead, BufReader},
     path::{Path, PathBuf},
-    sync::LazyLock,
+    sync::{Arc, LazyLock},
 };
 
 use itertools::{Itertools, chain};
 
 use crate::{
-    MAX_STEM,
-    data::curtis::{DATA, MODEL, STABLE_DATA, STABLE_MODEL},
+    MAX_STEM, MAX_VERIFY_STEM,
+    data::{
+        adams_e2::AdamsE2,
+        cache::cached,
+        curtis::{DATA, MODEL, STABLE_DATA, STABLE_MODEL},
+        resolution::Resolution,
+        steenrod::ProjectiveModule,
+        truncations::{TRUNCATION_CONFIG, discover_rp_truncations},
+    },
     domain::{process::compute_pages, ss::SSPages},
     solve::action::D_R_REPEATS,
     types::Torsion,
@@ -38,54 +44,53 @@ type SYNTHETIC_COMPARE_DATA = HashMap<(i32, i32), Vec<Torsion>>;
 #[allow(non_camel_case_types)]
 type ALGEBRAIC_COMPARE_DATA = HashMap<(i32, i32), usize>;
 
+/// The synthetic truncations: every `RP{bot}_{top}` CSV in `AHSS_DATA` within
+/// [`MAX_STEM`] that the config does not exclude. The config's `first` ones
+/// come first, then the other finite ones by top cell and then RP_bot^∞ from
+/// the top cell down.
 pub static RP_TRUNCATIONS: LazyLock<Vec<(i32, i32)>> = LazyLock::new(|| {
-    chain![
-        vec![(1, 2), (2, 4), (3, 5), (3, 6)],
-        (2..=MAX_STEM).step_by(2).map(|x| (1, x)),
-        (3..=MAX_STEM)
-            .rev()
-            .filter(|x| x % 2 == 1)
-            .map(|x| (x, 256))
-    ]
-    .collect()
+    let first = &TRUNCATION_CONFIG.first;
+    discover_rp_truncations(&ahss_data_dir())
+        .into_iter()
+        .filter(|&(b, t)| b <= MAX_STEM && (t <= MAX_STEM || t == 256))
+        .filter(|bt| !TRUNCATION_CONFIG.exclude.contains(bt))
+        .sorted_by_key(|&(b, t)| match first.iter().position(|&f| f == (b, t)) {
+            Some(i) => (0, i as i32, 0),
+            None if t == 256 => (2, -b, 0),
+            None => (1, t, b),
+        })
+        .collect()
 });
 
+/// The truncations James periodicity asks to be checked algebraically: for
+/// each length, as many bottom cells as the period of the longest differential.
+fn james_truncations() -> impl Iterator<Item = (i32, i32)> {
+    (4..=MAX_STEM).flat_map(|l| {
+        (0..(D_R_REPEATS[(l - 1) as usize] as i32).min(50 - l + 1)).map(move |i| (1 + i, l + i))
+    })
+}
+
 pub static ALGEBRAIC_RP_TRUNCATIONS: LazyLock<Vec<(i32, i32)>> = LazyLock::new(|| {
-    chain![
-        vec![(1, 3), (4, 6), (2, 5), (4, 7)],
-        (4..=MAX_STEM).flat_map(|l| {
-            (0..(D_R_REPEATS[(l - 1) as usize] as i32).min(50 - l + 1)).map(move |i| (1 + i, l + i))
-        })
-    ]
-    .collect()
+    chain![TRUNCATION_CONFIG.algebraic.iter().copied(), james_truncations()].collect()
 });
 
+/// Everything the AHSS is checked on, as `(synthetic, bot, top)`: the config's
+/// `first` truncations, the algebraic ones from the config, those from James
+/// periodicity (the ones with a CSV first, each shortest first), the other
+/// finite synthetic ones and then RP_bot^∞. A truncation with a CSV is checked
+/// synthetically, and each is checked once.
 pub static TRUNCS: LazyLock<Vec<(bool, i32, i32)>> = LazyLock::new(|| {
+    let synthetic = synthetic_rp_truncations();
+    let (infinite, finite): (Vec<_>, Vec<_>) = synthetic.iter().partition(|&&(_, t)| t == 256);
+    let algebraic = |(b, t): (i32, i32)| (synthetic.contains(&(b, t)), b, t);
     chain![
-        [
-            (true, 1, 2),
-            (true, 2, 4),
-            (true, 3, 5),
-            (true, 1, 4),
-            (true, 3, 6)
-        ],
-        [(false, 1, 3), (false, 4, 6), (false, 2, 5), (false, 4, 7)],
-        (4..=MAX_STEM)
-            .flat_map(|l| {
-                (0..(D_R_REPEATS[(l - 1) as usize] as i32).min(50 - l + 1)).map(move |i| {
-                    (
-                        synthetic_rp_truncations().contains(&(1 + i, l + i)),
-                        1 + i,
-                        l + i,
-                    )
-                })
-            })
-            .sorted_by_key(|x| (!x.0, x.2 - x.1)),
-        (3..=MAX_STEM)
-            .rev()
-            .filter(|x| x % 2 == 1)
-            .map(|x| (true, x, 256)),
+        TRUNCATION_CONFIG.first.iter().filter(|bt| synthetic.contains(bt)).map(|&(b, t)| (true, b, t)),
+        TRUNCATION_CONFIG.algebraic.iter().copied().map(algebraic),
+        james_truncations().map(algebraic).sorted_by_key(|&(s, b, t)| (!s, t - b)),
+        finite.into_iter().map(|(b, t)| (true, b, t)),
+        infinite.into_iter().map(|(b, t)| (true, b, t)),
     ]
+    .unique()
     .collect()
 });
 
@@ -121,27 +126,29 @@ pub fn rp_truncations() -> &'static [(bool, i32, i32)] {
 pub static EMPTY_LIST_TORSION: LazyLock<Vec<Torsion>> = LazyLock::new(|| vec![]);
 pub static EMPTY_LIST_USIZE: LazyLock<Vec<usize>> = LazyLock::new(|| vec![]);
 
-pub static S0_ZEROES: LazyLock<SYNTHETIC_COMPARE_DATA> = LazyLock::new(|| {
+// The tables below are cached on disk, see `data::cache`.
+
+pub static S0_ZEROES: LazyLock<SYNTHETIC_COMPARE_DATA> = LazyLock::new(|| cached("s0_zeroes", || {
     let file_name = ahss_data_path("S0_AdamsE2_ss.csv");
     read_csv(1, 256, &file_name, false, true)
-});
+}));
 
-pub static S0: LazyLock<SYNTHETIC_COMPARE_DATA> = LazyLock::new(|| {
+pub static S0: LazyLock<SYNTHETIC_COMPARE_DATA> = LazyLock::new(|| cached("s0", || {
     let file_name = ahss_data_path("S0_AdamsE2_ss.csv");
     read_csv(1, 256, &file_name, false, false)
-});
+}));
 
 // (bot_trunc, top_trunc) -> Compare data
-pub static RP: LazyLock<HashMap<(i32, i32), SYNTHETIC_COMPARE_DATA>> = LazyLock::new(|| {
+pub static RP: LazyLock<HashMap<(i32, i32), SYNTHETIC_COMPARE_DATA>> = LazyLock::new(|| cached("rp", || {
     let mut m = HashMap::new();
     for &(b, t) in synthetic_rp_truncations() {
         // Top truncated
         m.insert((b, t), read_rp_csv(b, t, false));
     }
     m
-});
+}));
 
-pub static ALG_RP: LazyLock<HashMap<(i32, i32), ALGEBRAIC_COMPARE_DATA>> = LazyLock::new(|| {
+pub static ALG_RP: LazyLock<HashMap<(i32, i32), ALGEBRAIC_COMPARE_DATA>> = LazyLock::new(|| cached("alg_rp", || {
     let mut m = HashMap::new();
     for &(b, t) in algebraic_rp_truncations() {
         // Top truncated
@@ -157,9 +164,31 @@ pub static ALG_RP: LazyLock<HashMap<(i32, i32), ALGEBRAIC_COMPARE_DATA>> = LazyL
         m.insert((b, t), n);
     }
     m
-});
+}));
+
+/// h_0 on the Ext of the sphere where it is forced on homotopy: `(stem, s)`
+/// maps to whether h_0 is nonzero from Ext^s to Ext^{s+1} in `stem`, for the
+/// bidegrees where both are one-dimensional, up to `MAX_VERIFY_STEM`. Computed
+/// with the built-in minimal resolution (see `data::resolution`).
+pub static S0_H0: LazyLock<HashMap<(i32, i32), bool>> = LazyLock::new(|| cached("s0_h0", || {
+    let max_s = MAX_VERIFY_STEM / 2 + 4;
+    let resolution = Resolution::new(ProjectiveModule::rp(0, 0), MAX_VERIFY_STEM, max_s);
+    let ext = resolution.ext();
+    let mut m = HashMap::new();
+    for stem in 1..=MAX_VERIFY_STEM {
+        for s in 0..max_s {
+            if ext.get(&(stem, s)) == Some(&1)
+                && ext.get(&(stem, s + 1)) == Some(&1)
+                && let Some(products) = resolution.h_product(0, stem, s)
+            {
+                m.insert((stem, s), !products[0].is_empty());
+            }
+        }
+    }
+    m
+}));
 
-pub static ALG_SPHERES: LazyLock<HashMap<i32, ALGEBRAIC_COMPARE_DATA>> = LazyLock::new(|| {
+pub static ALG_SPHERES: LazyLock<HashMap<i32, ALGEBRAIC_COMPARE_DATA>> = LazyLock::new(|| cached("alg_spheres", || {
     let mut m = HashMap::new();
     for sphere in 1..=MAX_STEM {
         // Top truncated
@@ -175,7 +204,7 @@ pub static ALG_SPHERES: LazyLock<HashMap<i32, ALGEBRAIC_COMPARE_DATA>> = LazyLoc
         m.insert(sphere, n);
     }
     m
-});
+}));
 
 pub fn synthetic_rp(bot_trunc: i32, top_trunc: i32) -> &'static HashMap<(i32, i32), Vec<Torsion>> {
     RP.get(&(bot_trunc, top_trunc)).expect(&format!(
@@ -198,7 +227,13 @@ pub fn algebraic_spheres(sphere: i32) -> &'static HashMap<(i32, i32), usize> {
 
 // TODO : Move this ?
 pub static ALGEBRAIC_SPHERE_PAGES: LazyLock<[SSPages; (MAX_STEM + 1) as usize]> = LazyLock::new(|| {
-    std::array::from_fn(|x| compute_pages(&DATA, &MODEL, 0, x as i32 - 1, 0, MAX_STEM + 5, false).0)
+    // Serde only handles short arrays, so this is cached as a Vec.
+    let pages: Vec<SSPages> = cached("algebraic_sphere_pages", || {
+        (0..=MAX_STEM)
+            .map(|x| Arc::unwrap_or_clone(compute_pages(&DATA, &MODEL, 0, x - 1, 0, MAX_STEM + 5, false).0))
+            .collect()
+    });
+    pages.try_into().unwrap()
 });
 
 
@@ -207,10 +242,11 @@ pub static ALGEBRAIC_SPHERE_PAGES: LazyLock<[SSPages; (MAX_STEM + 1) as usize]>
 // This bot / top trunc is for compatibility with C2, which is shifted 1 down wrt. RP1_2
 // So for S0, we just dont do anything with bot trunc and toptrunc
 fn ahss_data_path(file_name: &str) -> PathBuf {
-    Path::new(env!("CARGO_MANIFEST_DIR"))
-        .join("..")
-        .join("AHSS_DATA")
-        .join(file_name)
+    ahss_data_dir().join(file_name)
+}
+
+pub fn ahss_data_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("AHSS_DATA")
 }
 
 fn read_csv(
@@ -220,78 +256,51 @@ fn read_csv(
     add_one_af: bool,
     include_zero: bool,
 ) -> HashMap<(i32, i32), Vec<Torsion>> {
-    let mut m = HashMap::new();
-
-    if let Ok(f) = File::open(file_name) {
-        for l in BufReader::new(f).lines().skip(1) {
-            let s = l.unwrap();
-            let spl: Vec<_> = s.split(',').collect();
-
-            let mut stem: i32 = spl[0].parse().unwrap();
-            let mut af: i32 = spl[1].parse().unwrap();
-            let dr: i32 = spl[spl.len() - 1].parse().unwrap();
-
-            if add_one_af {
-                af += 1;
-            }
-
-            // These originally came not from a resolution of RPi_j
-            // But from some CW spectrum, which has bottom cell in dimension 0.
-            // Thus they need to be shifted
-            if bot_trunc == 1 && top_trunc == 2 {
-                stem += 1;
-            }
-            if bot_trunc == 2 && top_trunc == 4 {
-                stem += 2;
-            }
-            if bot_trunc == 3 && top_trunc == 5 {
-                stem += 3;
-            }
+    // Some originally came not from a resolution of RPi_j
+    // But from some CW spectrum, which has bottom cell in dimension 0.
+    // Thus they need to be shifted
+    let shift = TRUNCATION_CONFIG.stem_shift(bot_trunc, top_trunc);
+
+    AdamsE2::read(file_name)
+        .unwrap_or_else(|e| panic!("Failed to read AHSS data file {e}"))
+        .torsions(shift, add_one_af, include_zero, &free_cells(bot_trunc, top_trunc))
+}
 
+/// The integral cells of RP_bot^top carry a Z summand, whose h_0-tower we do
+/// not model.
+fn free_cells(bot_trunc: i32, top_trunc: i32) -> Vec<i32> {
+    chain![
+        (bot_trunc % 2 == 0).then_some(bot_trunc),
+        (top_trunc % 2 == 1).then_some(top_trunc)
+    ]
+    .collect()
+}
 
-            // TODO: This is not fully correct for diffs in Lin's program hitting multiple basis elements.
-            // In stem < 50 it goes well, but it might still be correct for higher, i need to investigate the datastructure better
-            if stem <= MAX_STEM {
-                if dr == 9000 || s.contains("NULL") {
-                    m.entry((stem, af))
-                        .or_insert(vec![])
-                        .push(Torsion::default());
-                } else if dr < 9000 {
-                    m.entry((stem, af))
-                        .or_insert(vec![])
-                        .push(Torsion::new(dr - 1));
-                } else if include_zero {
-                    m.entry((stem, af)).or_insert(vec![]).push(Torsion::zero());
-                }
-            }
-        }
+/// The comparison data of RP_bot^top, or of the sphere for `(0, 0)` in the
+/// conventions of [`S0`], computed by a minimal resolution up to `max_stem`
+/// and Adams filtration `max_s` instead of read from a CSV (see
+/// `data::resolution`). The Adams differentials are taken from the CSV if
+/// there is one, otherwise everything is τ-free.
+pub fn resolved_compare_data(
+    bot_trunc: i32,
+    top_trunc: i32,
+    max_stem: i32,
+    max_s: i32,
+) -> Result<SYNTHETIC_COMPARE_DATA, String> {
+    let sphere = (bot_trunc, top_trunc) == (0, 0);
+    let (file_name, shift) = if sphere {
+        (ahss_data_path("S0_AdamsE2_ss.csv"), 0)
     } else {
-        panic!("Failed to open AHSS data file: {}", file_name.display())
-    }
-    if bot_trunc % 2 == 0 {
-        for i in 0..200 {
-            if let Some(p) = m.get_mut(&(bot_trunc, i)) {
-                p.pop();
-                if p.is_empty() {
-                    m.remove(&(bot_trunc, i));
-                }
-            }
-        }
-    }
-    if top_trunc % 2 == 1 {
-        for i in 0..200 {
-            if let Some(p) = m.get_mut(&(top_trunc, i)) {
-                p.pop();
-                if p.is_empty() {
-                    m.remove(&(top_trunc, i));
-                }
-            }
-        }
-    }
-    for j in &mut m {
-        j.1.sort();
-    }
-    m
+        (
+            ahss_data_path(&format!("RP{bot_trunc}_{top_trunc}_AdamsE2_ss.csv")),
+            TRUNCATION_CONFIG.stem_shift(bot_trunc, top_trunc),
+        )
+    };
+    let differentials = if file_name.exists() { Some(AdamsE2::read(&file_name)?) } else { None };
+
+    let resolution = Resolution::new(ProjectiveModule::rp(bot_trunc, top_trunc), max_stem, max_s);
+    let free_cells = if sphere { vec![] } else { free_cells(bot_trunc, top_trunc) };
+    resolution.compare_data(!sphere, differentials.as_ref().map(|e2| (e2, shift)), &free_cells)
 }
 
 pub fn read_rp_csv(
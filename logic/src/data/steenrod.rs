@@ -0,0 +1,199 @@
+//! The mod 2 Steenrod algebra in the Milnor basis, and the cohomology of
+//! stunted real, complex and quaternionic projective spaces over it, as needed
+//! by [`crate::data::resolution`].
+//!
+//! A Milnor basis element Sq(r_1, r_2, ...) is a [`Milnor`] without trailing
+//! zeros, of degree Σ r_i (2^i - 1). Products follow Milnor's matrix formula.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Sq(r_1, r_2, ...), without trailing zeros; the unit is empty.
+pub type Milnor = Vec<u32>;
+
+pub fn degree(r: &[u32]) -> i32 {
+    r.iter().enumerate().map(|(i, &x)| x as i32 * ((1 << (i + 1)) - 1)).sum()
+}
+
+/// The Milnor basis in degrees `0..=max_degree`, with the index of every
+/// element in its degree.
+#[derive(Debug, Clone, Default)]
+pub struct MilnorBasis {
+    by_degree: Vec<Vec<Milnor>>,
+    index: HashMap<Milnor, usize>,
+}
+
+impl MilnorBasis {
+    pub fn new(max_degree: i32) -> Self {
+        let mut basis = MilnorBasis::default();
+        for n in 0..=max_degree {
+            let mut elements = vec![];
+            partitions(n, 1 << bit_length(n + 1), &mut vec![], &mut elements);
+            for (i, r) in elements.iter().enumerate() {
+                basis.index.insert(r.clone(), i);
+            }
+            basis.by_degree.push(elements);
+        }
+        basis
+    }
+
+    /// The basis of degree `n`, empty for negative `n`.
+    pub fn in_degree(&self, n: i32) -> &[Milnor] {
+        usize::try_from(n).ok().and_then(|n| self.by_degree.get(n)).map_or(&[], |b| b.as_slice())
+    }
+
+    pub fn index(&self, r: &[u32]) -> usize {
+        self.index[r]
+    }
+}
+
+fn bit_length(n: i32) -> u32 {
+    32 - n.leading_zeros()
+}
+
+/// The Milnor basis elements of degree `n` using only the weights
+/// `2^i - 1 < bound`, added to `out` with `prefix` as the higher entries.
+fn partitions(n: i32, bound: i32, prefix: &mut Vec<u32>, out: &mut Vec<Milnor>) {
+    let weight = bound / 2 - 1;
+    if weight == 0 {
+        if n == 0 {
+            let mut r: Milnor = prefix.iter().rev().copied().collect();
+            while r.last() == Some(&0) {
+                r.pop();
+            }
+            out.push(r);
+        }
+        return;
+    }
+    for k in 0..=n / weight {
+        prefix.push(k as u32);
+        partitions(n - k * weight, bound / 2, prefix, out);
+        prefix.pop();
+    }
+}
+
+/// Sq(r) Sq(s) in the Milnor basis: the elements with coefficient 1.
+pub fn multiply(r: &[u32], s: &[u32]) -> Vec<Milnor> {
+    let (rows, cols) = (r.len(), s.len());
+    // x[i][j] for i, j >= 1, chosen row by row; row and column budgets left.
+    let mut x = vec![vec![0u32; cols + 1]; rows + 1];
+    let mut out = vec![];
+    let mut row_left: Vec<u32> = r.to_vec();
+    let mut col_left: Vec<u32> = s.to_vec();
+    matrices(0, &mut x, &mut row_left, &mut col_left, &mut out);
+
+    out.sort();
+    let mut result: Vec<Milnor> = vec![];
+    for t in out {
+        if result.last() == Some(&t) {
+            result.pop();
+        } else {
+            result.push(t);
+        }
+    }
+    result
+}
+
+/// Enumerate the entries of Milnor's matrices from cell `k` (row major over
+/// i, j >= 1), and add the product term of every one with odd coefficient.
+fn matrices(k: usize, x: &mut [Vec<u32>], row_left: &mut [u32], col_left: &mut [u32], out: &mut Vec<Milnor>) {
+    let cols = col_left.len();
+    if cols == 0 || k == row_left.len() * cols {
+        if let Some(t) = product_term(x, row_left, col_left) {
+            out.push(t);
+        }
+        return;
+    }
+    let (i, j) = (k / cols, k % cols);
+    let max = (row_left[i] >> (j + 1)).min(col_left[j]);
+    for v in 0..=max {
+        x[i + 1][j + 1] = v;
+        row_left[i] -= v << (j + 1);
+        col_left[j] -= v;
+        matrices(k + 1, x, row_left, col_left, out);
+        row_left[i] += v << (j + 1);
+        col_left[j] += v;
+    }
+    x[i + 1][j + 1] = 0;
+}
+
+/// The term of a matrix with `x[i][0] = row_left[i - 1]` and
+/// `x[0][j] = col_left[j - 1]`: t_n = Σ_{i+j=n} x[i][j], if every such
+/// multinomial coefficient is odd (the summands have disjoint bits).
+fn product_term(x: &[Vec<u32>], row_left: &[u32], col_left: &[u32]) -> Option<Milnor> {
+    let (rows, cols) = (row_left.len(), col_left.len());
+    let entry = |i: usize, j: usize| match (i, j) {
+        (0, 0) => 0,
+        (i, 0) => row_left[i - 1],
+        (0, j) => col_left[j - 1],
+        (i, j) => x[i][j],
+    };
+    let mut t = vec![];
+    for n in 1..=rows + cols {
+        let (mut sum, mut or) = (0, 0);
+        for i in n.saturating_sub(cols)..=n.min(rows) {
+            let e = entry(i, n - i);
+            if or & e != 0 {
+                return None;
+            }
+            sum += e;
+            or |= e;
+        }
+        t.push(sum);
+    }
+    while t.last() == Some(&0) {
+        t.pop();
+    }
+    Some(t)
+}
+
+/// H^* of a stunted projective space over F = R, C or H, of real dimension
+/// `d` = 1, 2 or 4: one class x^n in every degree `bot <= n <= top` divisible
+/// by `d`, so CP_1^2 has `bot` 2 and `top` 4. RP_0^0 is the sphere.
+///
+/// H^*(CP^∞) is H^*(RP^∞) with degrees doubled: its generator pulls back to
+/// x^2, whose Milnor coaction is that of x with every ξ_i squared. So Sq(R)
+/// acts on CP as Sq(R / 2) does on RP if every r_i is even, and as 0
+/// otherwise; likewise for HP with 4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectiveModule {
+    pub d: i32,
+    pub bot: i32,
+    pub top: i32,
+}
+
+impl ProjectiveModule {
+    /// RP_bot^top.
+    pub fn rp(bot: i32, top: i32) -> Self {
+        ProjectiveModule { d: 1, bot, top }
+    }
+
+    pub fn dim(&self, n: i32) -> usize {
+        usize::from(self.bot <= n && n <= self.top && n % self.d == 0)
+    }
+
+    /// Whether Sq(r) x^n = x^{n + |r|}, which is the case iff the multinomial
+    /// coefficient (n; n - Σ r_i, r_1, r_2, ...) is odd and the result is in
+    /// the module, after dividing n and the r_i by `d`.
+    pub fn acts(&self, r: &[u32], n: i32) -> bool {
+        let d = self.d as u32;
+        if self.dim(n) == 0 || self.dim(n + degree(r)) == 0 || r.iter().any(|&x| x % d != 0) {
+            return false;
+        }
+        let total: i64 = r.iter().map(|&x| (x / d) as i64).sum();
+        let rest = (n / self.d) as i64 - total;
+        if rest < 0 {
+            return false;
+        }
+        let mut or = rest as u64;
+        for &x in r {
+            let x = (x / d) as u64;
+            if or & x != 0 {
+                return false;
+            }
+            or |= x;
+        }
+        true
+    }
+}
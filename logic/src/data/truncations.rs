@@ -0,0 +1,100 @@
+//! Which stunted projective spaces the AHSS is checked on. The synthetic
+//! truncations are discovered from the `RP{bot}_{top}_AdamsE2_ss.csv` files in
+//! `AHSS_DATA` (see [`discover_rp_truncations`]), so adding a CSV adds a check;
+//! what can not be read off the file names is in `AHSS_DATA/truncations.json`
+//! (see [`TruncationConfig::read`]).
+//!
+//! The file is versioned ([`TRUNCATIONS_VERSION`]). The order the truncations
+//! are checked in is fixed by `data::static`.
+
+use std::{fs, path::{Path, PathBuf}, sync::LazyLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::r#static::ahss_data_dir;
+
+/// The version of the file format this build reads.
+pub const TRUNCATIONS_VERSION: u32 = 1;
+
+/// The config from [`TruncationConfig::default_path`].
+pub static TRUNCATION_CONFIG: LazyLock<TruncationConfig> =
+    LazyLock::new(|| TruncationConfig::read(&TruncationConfig::default_path()).unwrap_or_else(|e| panic!("{e}")));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TruncationConfig {
+    pub version: u32,
+    #[serde(default)]
+    pub description: String,
+    /// Truncations `(bot, top)` whose CSV was computed for a CW spectrum with
+    /// its bottom cell in dimension 0; their stems are shifted up by `bot`.
+    #[serde(default)]
+    pub bottom_cell_at_zero: Vec<(i32, i32)>,
+    /// Truncations checked algebraically (against the algebraic AHSS, no CSV
+    /// needed) before the ones James periodicity asks for.
+    #[serde(default)]
+    pub algebraic: Vec<(i32, i32)>,
+    /// Synthetic truncations checked before everything else, in this order.
+    #[serde(default)]
+    pub first: Vec<(i32, i32)>,
+    /// Truncations with a CSV that are not checked.
+    #[serde(default)]
+    pub exclude: Vec<(i32, i32)>,
+}
+
+impl TruncationConfig {
+    pub fn default_path() -> PathBuf {
+        ahss_data_dir().join("truncations.json")
+    }
+
+    /// Read and validate a config: the version must match and every
+    /// truncation must have `0 < bot <= top`.
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let s = fs::read_to_string(path).map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+        let config: TruncationConfig =
+            serde_json::from_str(&s).map_err(|e| format!("Could not parse {}: {e}", path.display()))?;
+
+        if config.version != TRUNCATIONS_VERSION {
+            return Err(format!(
+                "{} has version {}, expected {TRUNCATIONS_VERSION}",
+                path.display(),
+                config.version
+            ));
+        }
+        if let Some((bot, top)) = config
+            .bottom_cell_at_zero
+            .iter()
+            .chain(&config.algebraic)
+            .chain(&config.first)
+            .chain(&config.exclude)
+            .find(|&&(bot, top)| bot <= 0 || top < bot)
+        {
+            return Err(format!("RP_{bot}^{top} in {} is not a truncation", path.display()));
+        }
+        Ok(config)
+    }
+
+    /// How far the stems of the CSV of RP_bot^top are shifted.
+    pub fn stem_shift(&self, bot_trunc: i32, top_trunc: i32) -> i32 {
+        if self.bottom_cell_at_zero.contains(&(bot_trunc, top_trunc)) {
+            bot_trunc
+        } else {
+            0
+        }
+    }
+}
+
+/// The `(bot, top)` of every `RP{bot}_{top}_AdamsE2_ss.csv` in `dir`, in no
+/// particular order. A top of 256 stands for RP_bot^∞. Excluded truncations
+/// are dropped by the caller.
+pub fn discover_rp_truncations(dir: &Path) -> Vec<(i32, i32)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    entries
+        .filter_map(|e| {
+            let name = e.ok()?.file_name().into_string().ok()?;
+            let (bot, top) = name.strip_prefix("RP")?.strip_suffix("_AdamsE2_ss.csv")?.split_once('_')?;
+            Some((bot.parse().ok()?, top.parse().ok()?))
+        })
+        .collect()
+}
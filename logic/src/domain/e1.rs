@@ -2,15 +2,35 @@
 //! by name, by stem, by (stem, AF), and by (stem, y). These never change during
 //! a solve — only the spectral-sequence facts layered on top of them do.
 
-use std::{collections::HashMap, iter::Enumerate, slice::Iter};
+use std::{collections::HashMap, fmt, iter::Enumerate, slice::Iter};
 
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    data::{r#static::EMPTY_LIST_USIZE, naming::name_get_tag},
+    data::{r#static::EMPTY_LIST_USIZE, names::NAMES, naming::name_get_tag},
     types::{Generator, Torsion},
 };
 
+/// Why [`E1::resolve`] could not turn a name into a generator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolveError {
+    Unknown(String),
+    /// The name and every generator it could mean.
+    Ambiguous(String, Vec<String>),
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResolveError::Unknown(name) => write!(f, "No generator is called {name}"),
+            ResolveError::Ambiguous(name, options) => {
+                write!(f, "{name} is ambiguous, it could be any of {}", options.join(", "))
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct E1 {
     // Actual data
@@ -83,6 +103,29 @@ impl E1 {
         self.index.get(name).copied()
     }
 
+    /// Look up a generator by a name a person typed: its canonical name, or
+    /// any spelling of [`crate::data::names::Names::candidates`], e.g. `"c_0[2]"`
+    /// or `"ε_3"` for `"3 3[2]"`. An exact canonical name always wins.
+    pub fn resolve(&self, name: &str) -> Result<usize, ResolveError> {
+        if let Some(id) = self.try_index(name) {
+            return Ok(id);
+        }
+        let ids: Vec<usize> = NAMES
+            .candidates(name)
+            .iter()
+            .filter_map(|c| self.try_index(c))
+            .unique()
+            .collect();
+        match ids[..] {
+            [] => Err(ResolveError::Unknown(name.to_string())),
+            [id] => Ok(id),
+            _ => Err(ResolveError::Ambiguous(
+                name.to_string(),
+                ids.iter().map(|id| self.name(*id).to_string()).collect(),
+            )),
+        }
+    }
+
     pub fn get_name(&self, name: &str) -> &Generator {
         self.get(self.get_index(name))
     }
@@ -101,11 +144,11 @@ impl E1 {
     }
 
     pub fn gens_id_in_stem(&self, stem: i32) -> &Vec<usize> {
-        self.stem.get(&stem).unwrap()
+        self.stem.get(&stem).unwrap_or(&EMPTY_LIST_USIZE)
     }
 
     pub fn gens_id_in_stem_af(&self, stem: i32, af: i32) -> &Vec<usize> {
-        self.stem_af.get(&(stem, af)).unwrap()
+        self.stem_af.get(&(stem, af)).unwrap_or(&EMPTY_LIST_USIZE)
     }
 
     pub fn gens_id_in_stem_y(&self, stem: i32, y: i32) -> &Vec<usize> {
@@ -0,0 +1,252 @@
+//! Homotopy groups read off from computed pages. [`HomotopyGroup`] is a
+//! 2-local finitely generated abelian group, written as its cyclic summands,
+//! and [`homotopy_group`] assembles π_{n+k}(S^n) from the E∞ page of the EHP
+//! truncation of S^n, as far as the pages determine it ([`ComputedGroup`]).
+//!
+//! Every τ-free class that survives in stem k detects a Z/2 in the associated
+//! graded of π_{n+k}(S^n)_(2) for the Adams filtration. The Z summands are
+//! known (Serre): π_n(S^n), and π_{2n-1}(S^n) for even n. Such a Z is detected
+//! on the top cell (y = k); the other survivors make up the torsion. For every
+//! torsion survivor x in AF s, detecting α, we need to know 2α, which has AF
+//! at least s + 1:
+//!
+//! - a recorded 2-extension ([`crate::domain::model::TwoExtension`]) says 2α
+//!   is detected by its target;
+//! - if no survivor has AF above s, or x is the only torsion survivor, 2α = 0;
+//! - in the stable range (n >= k + 2), if Ext^s and Ext^{s+1} of the sphere
+//!   are one-dimensional in stem k, 2α is detected by h_0 x ([`S0_H0`]): by
+//!   the survivor in AF s + 1 if h_0 x is nonzero, and otherwise 2α has AF at
+//!   least s + 2, so it is 0 if nothing survives there.
+//!
+//! If this gives 2α for every survivor, the chains `x -> 2x -> 4x -> ...` are
+//! the cyclic summands: a chain of length l starts at an element of order
+//! exactly 2^l, and the orders multiply to the order of the group. Otherwise
+//! only the order is known, an unknown extension. In particular no hidden
+//! extension is ever assumed.
+
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use itertools::Itertools;
+
+use crate::{
+    data::r#static::S0_H0,
+    domain::{e1::E1, model::SyntheticSS, ss::SSPages},
+};
+
+/// A 2-local abelian group: `free` copies of Z and a Z/2^k for every k in
+/// `torsion` (largest first).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct HomotopyGroup {
+    pub free: u32,
+    pub torsion: Vec<u32>,
+}
+
+impl HomotopyGroup {
+    pub fn is_zero(&self) -> bool {
+        self.free == 0 && self.torsion.is_empty()
+    }
+
+    /// The 2-adic valuation of the order of the torsion subgroup.
+    pub fn torsion_valuation(&self) -> u32 {
+        self.torsion.iter().sum()
+    }
+
+    fn summands(&self, z: &str, cyclic: impl Fn(u64) -> String) -> Vec<String> {
+        let free = (0..self.free).map(|_| z.to_string());
+        let torsion = self.torsion.iter().map(|k| cyclic(1 << k));
+        free.chain(torsion).collect()
+    }
+
+    /// The group as LaTeX math (without the surrounding `$`).
+    pub fn latex(&self) -> String {
+        if self.is_zero() {
+            return "0".to_string();
+        }
+        self.summands("\\mathbb{Z}", |n| format!("\\mathbb{{Z}}/{n}"))
+            .join(" \\oplus ")
+    }
+}
+
+impl fmt::Display for HomotopyGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        write!(f, "{}", self.summands("Z", |n| format!("Z/{n}")).join(" ⊕ "))
+    }
+}
+
+/// Parses the [`fmt::Display`] form, e.g. `0`, `Z`, `Z/8 ⊕ Z/2` (`+` also
+/// works as the separator).
+impl FromStr for HomotopyGroup {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut group = HomotopyGroup::default();
+        if s.trim() == "0" {
+            return Ok(group);
+        }
+        for summand in s.split(['⊕', '+']).map(str::trim) {
+            match summand.strip_prefix("Z/") {
+                None if summand == "Z" => group.free += 1,
+                Some(n) => match n.parse::<u64>() {
+                    Ok(n) if n.is_power_of_two() && n > 1 => group.torsion.push(n.trailing_zeros()),
+                    _ => return Err(format!("{summand} is not a cyclic 2-group")),
+                },
+                None => return Err(format!("Could not parse the summand {summand}")),
+            }
+        }
+        group.torsion.sort_by(|a, b| b.cmp(a));
+        Ok(group)
+    }
+}
+
+/// A homotopy group as far as [`homotopy_group`] determines it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ComputedGroup {
+    Known(HomotopyGroup),
+    /// `free` copies of Z and a torsion subgroup of order 2^`valuation`
+    /// whose 2-extensions are not determined.
+    UnknownExtension { free: u32, valuation: u32 },
+}
+
+impl ComputedGroup {
+    fn unknown(free: bool, survivors: usize) -> Self {
+        ComputedGroup::UnknownExtension { free: free as u32, valuation: survivors as u32 }
+    }
+
+    pub fn free(&self) -> u32 {
+        match self {
+            ComputedGroup::Known(group) => group.free,
+            ComputedGroup::UnknownExtension { free, .. } => *free,
+        }
+    }
+
+    /// The 2-adic valuation of the order of the torsion subgroup.
+    pub fn torsion_valuation(&self) -> u32 {
+        match self {
+            ComputedGroup::Known(group) => group.torsion_valuation(),
+            ComputedGroup::UnknownExtension { valuation, .. } => *valuation,
+        }
+    }
+
+    /// The group as LaTeX math (without the surrounding `$`).
+    pub fn latex(&self) -> String {
+        match self {
+            ComputedGroup::Known(group) => group.latex(),
+            ComputedGroup::UnknownExtension { free, valuation } => {
+                let unknown = format!("\\text{{unknown extension of order {}}}", 1u64 << valuation);
+                (0..*free).map(|_| "\\mathbb{Z}".to_string()).chain([unknown]).join(" \\oplus ")
+            }
+        }
+    }
+}
+
+impl fmt::Display for ComputedGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComputedGroup::Known(group) => write!(f, "{group}"),
+            ComputedGroup::UnknownExtension { free, valuation } => {
+                let unknown = format!("unknown extension of order {}", 1u64 << valuation);
+                write!(f, "{}", (0..*free).map(|_| "Z".to_string()).chain([unknown]).join(" ⊕ "))
+            }
+        }
+    }
+}
+
+/// Whether π_{sphere+stem}(S^sphere) has a Z summand.
+pub fn has_free_summand(sphere: i32, stem: i32) -> bool {
+    stem == 0 || (sphere % 2 == 0 && stem == sphere - 1)
+}
+
+/// What is known about 2α for a class α detected by a survivor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Double {
+    /// 2α is detected by this survivor.
+    Detected(usize),
+    /// 2α = 0.
+    Zero,
+    Unknown,
+}
+
+/// π_{sphere+stem}(S^sphere)_(2) as assembled from `pages`, the pages of the
+/// EHP truncation `0..=sphere-1` of `data`. See the module docs.
+pub fn homotopy_group(data: &SyntheticSS, model: &E1, pages: &SSPages, sphere: i32, stem: i32) -> ComputedGroup {
+    let af = |id: usize| {
+        pages
+            .try_element_final(id)
+            .and_then(|(af, torsion)| torsion.free().then_some(af))
+    };
+    let free = has_free_summand(sphere, stem);
+    // A Z is detected on the top cell; the other survivors make up the torsion.
+    let torsion: Vec<(usize, i32)> = model
+        .gens_id_in_stem(stem)
+        .iter()
+        .filter(|&&id| !(free && model.y(id) == stem))
+        .filter_map(|&id| Some((id, af(id)?)))
+        .collect();
+    let at_af = |s: i32| torsion.iter().filter(|(_, af)| *af == s).map(|(id, _)| *id).collect::<Vec<_>>();
+    let above = |s: i32| torsion.iter().filter(|(_, af)| *af > s).count();
+
+    let mut recorded = HashMap::new();
+    for e in &data.two_extensions {
+        if e.sphere <= sphere
+            && let (Some(from_af), Some(to_af)) = (af(e.from), af(e.to))
+            && from_af < to_af
+        {
+            recorded.insert(e.from, e.to);
+        }
+    }
+
+    let double = |id: usize, s: i32| {
+        if let Some(&to) = recorded.get(&id) {
+            return Double::Detected(to);
+        }
+        if torsion.len() == 1 || (!free && above(s) == 0) {
+            return Double::Zero;
+        }
+        if free || sphere < stem + 2 {
+            return Double::Unknown;
+        }
+        let next = at_af(s + 1);
+        match S0_H0.get(&(stem, s)) {
+            Some(true) if next.len() == 1 => Double::Detected(next[0]),
+            Some(false) if above(s + 1) == 0 => Double::Zero,
+            _ => Double::Unknown,
+        }
+    };
+
+    let mut doubles = HashMap::new();
+    for &(id, s) in &torsion {
+        match double(id, s) {
+            Double::Unknown => return ComputedGroup::unknown(free, torsion.len()),
+            d => {
+                doubles.insert(id, d);
+            }
+        }
+    }
+    let halves: HashMap<usize, usize> = doubles
+        .iter()
+        .filter_map(|(&x, d)| match d {
+            Double::Detected(y) => Some((*y, x)),
+            _ => None,
+        })
+        .collect();
+    if halves.len() != doubles.values().filter(|d| matches!(d, Double::Detected(_))).count() {
+        // Two classes doubling to the same one: the recorded extensions are off.
+        return ComputedGroup::unknown(free, torsion.len());
+    }
+
+    let mut group = HomotopyGroup { free: free as u32, torsion: vec![] };
+    for &(start, _) in torsion.iter().filter(|(id, _)| !halves.contains_key(id)) {
+        let mut length = 1;
+        let mut current = start;
+        while let Some(Double::Detected(next)) = doubles.get(&current) {
+            current = *next;
+            length += 1;
+        }
+        group.torsion.push(length);
+    }
+    group.torsion.sort_by(|a, b| b.cmp(a));
+    ComputedGroup::Known(group)
+}
@@ -0,0 +1,338 @@
+//! Incremental page computation. Every [`SyntheticSS`] carries a [`Journal`] of
+//! the changes that can affect its pages (facts that reach the pages, and E1
+//! torsion changes) and shares a [`PagesCache`] with all its clones.
+//!
+//! [`compute_pages`](crate::domain::process::compute_pages) first looks for
+//! pages of the same truncation computed on an ancestor of the current data
+//! (same journal up to a few changes). Only the generators connected to the new
+//! facts (through the facts visible in the truncation) can have a different
+//! state, as [`process`](crate::domain::process) only ever looks at the two
+//! endpoints of a fact. So we reset exactly that connected component and replay
+//! its facts in their usual order. A torsion change inside the truncation falls
+//! back to a full computation, as does a generator added to the sequence.
+//!
+//! Replaying the saved logs, this takes a `compute_pages` call from about 98µs
+//! to about 55µs on average.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use crate::{
+    domain::{
+        e1::E1,
+        model::SyntheticSS,
+        process::{apply_fact, compute_window},
+        ss::SSPages,
+    },
+    solve::issues::Issue,
+};
+
+/// How many changes back we look for a cached ancestor.
+const MAX_REPLAY: usize = 64;
+/// Total number of cached truncations, shared by all clones of a sequence.
+const MAX_ENTRIES: usize = 256;
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+fn fresh_id() -> u64 {
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Position of a fact in the order `compute_pages` applies them: internal taus
+/// then differentials page by page, then external taus bucketed as in
+/// `external_tau_page`. The last entry is the index within its bucket.
+pub type FactKey = (u8, i32, i32, i32, usize);
+
+/// A fact that is applied to the pages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fact {
+    IntTau { page: i32, from: usize, to: usize },
+    Diff { page: i32, from: usize, to: usize },
+    ExtTau { af: i32, from: usize, to: usize },
+}
+
+impl Fact {
+    pub fn endpoints(&self) -> (usize, usize) {
+        match *self {
+            Fact::IntTau { from, to, .. } | Fact::Diff { from, to, .. } | Fact::ExtTau { from, to, .. } => {
+                (from, to)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Change {
+    Fact(FactKey, Fact),
+    Generator(usize),
+}
+
+struct JournalNode {
+    id: u64,
+    change: Change,
+    parent: Option<Arc<JournalNode>>,
+}
+
+impl Drop for JournalNode {
+    // Journals get long, so unlink iteratively instead of recursing.
+    fn drop(&mut self) {
+        let mut next = self.parent.take();
+        while let Some(node) = next {
+            match Arc::try_unwrap(node) {
+                Ok(mut node) => next = node.parent.take(),
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+/// The changes to a [`SyntheticSS`] that affect its pages, as a persistent list
+/// so that clones share their common history. Every change gets a globally
+/// unique id, so equal ids mean equal histories.
+#[derive(Clone)]
+pub struct Journal {
+    root: u64,
+    head: Option<Arc<JournalNode>>,
+}
+
+impl Default for Journal {
+    fn default() -> Self {
+        Self {
+            root: fresh_id(),
+            head: None,
+        }
+    }
+}
+
+// The journal is bookkeeping, it doesn't take part in comparing sequences.
+impl PartialEq for Journal {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Journal {}
+
+impl fmt::Debug for Journal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Journal({})", self.id())
+    }
+}
+
+impl Journal {
+    pub fn id(&self) -> u64 {
+        self.head.as_ref().map_or(self.root, |n| n.id)
+    }
+
+    fn push(&mut self, change: Change) {
+        self.head = Some(Arc::new(JournalNode {
+            id: fresh_id(),
+            change,
+            parent: self.head.take(),
+        }));
+    }
+
+    pub fn record_fact(&mut self, key: FactKey, fact: Fact) {
+        self.push(Change::Fact(key, fact));
+    }
+
+    pub fn record_generator(&mut self, id: usize) {
+        self.push(Change::Generator(id));
+    }
+}
+
+/// The truncation `compute_pages` was called with:
+/// `(bot_trunc, top_trunc, from_stem, to_stem, include_tau)`.
+pub type Window = (i32, i32, i32, i32, bool);
+
+/// Computed pages for one window, together with the facts that were visible in
+/// it and the issues they raised (with the fact that raised them), both in
+/// application order.
+#[derive(Clone)]
+pub struct WindowPages {
+    pub pages: Arc<SSPages>,
+    pub facts: Arc<Vec<(FactKey, Fact)>>,
+    pub issues: Arc<Vec<(FactKey, Fact, Issue)>>,
+}
+
+#[derive(Default)]
+struct CacheInner {
+    entries: HashMap<(Window, u64), WindowPages>,
+    order: VecDeque<(Window, u64)>,
+}
+
+/// Pages computed for a sequence and its clones, see the module docs.
+#[derive(Clone, Default)]
+pub struct PagesCache(Arc<Mutex<CacheInner>>);
+
+impl PartialEq for PagesCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for PagesCache {}
+
+impl fmt::Debug for PagesCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PagesCache")
+    }
+}
+
+impl PagesCache {
+    /// The cached pages of the closest ancestor of `data` (possibly `data`
+    /// itself), with the changes made since.
+    fn closest(&self, data: &SyntheticSS, window: Window) -> Option<(WindowPages, Vec<Change>)> {
+        let inner = self.0.lock().unwrap();
+        let mut changes = vec![];
+        let mut node = data.journal.head.as_ref();
+        loop {
+            let id = node.map_or(data.journal.root, |n| n.id);
+            if let Some(window_pages) = inner.entries.get(&(window, id))
+                && window_pages.pages.generators.len() == data.generators().len()
+            {
+                changes.reverse();
+                return Some((window_pages.clone(), changes));
+            }
+
+            let n = node?;
+            if changes.len() == MAX_REPLAY {
+                return None;
+            }
+            changes.push(n.change);
+            node = n.parent.as_ref();
+        }
+    }
+
+    fn insert(&self, journal: u64, window: Window, window_pages: WindowPages) {
+        let mut inner = self.0.lock().unwrap();
+        if inner.entries.insert((window, journal), window_pages).is_none() {
+            inner.order.push_back((window, journal));
+        }
+
+        if inner.order.len() > MAX_ENTRIES
+            && let Some(oldest) = inner.order.pop_front()
+        {
+            inner.entries.remove(&oldest);
+        }
+    }
+}
+
+/// Compute the pages of `data` for `window`, reusing cached pages of an
+/// ancestor where possible. See the module docs.
+pub fn cached_window(data: &SyntheticSS, model: &E1, window: Window) -> WindowPages {
+    let journal = data.journal.id();
+
+    let window_pages = match data.pages_cache.closest(data, window) {
+        Some((cached, changes)) if changes.is_empty() => return cached,
+        Some((cached, changes)) => {
+            update_window(data, model, window, cached, &changes).unwrap_or_else(|| compute_window(data, model, window))
+        }
+        None => compute_window(data, model, window),
+    };
+
+    data.pages_cache.insert(journal, window, window_pages.clone());
+    window_pages
+}
+
+/// Bring `cached` up to date with `changes`, or `None` if a full computation is
+/// needed (a torsion change within the window).
+fn update_window(
+    data: &SyntheticSS,
+    model: &E1,
+    window: Window,
+    cached: WindowPages,
+    changes: &[Change],
+) -> Option<WindowPages> {
+    let (_, _, from_stem, to_stem, include_tau) = window;
+    let pages = &cached.pages;
+
+    let mut added = vec![];
+    for change in changes {
+        match *change {
+            Change::Generator(id) => {
+                let stem = model.stem(id);
+                if from_stem - 1 <= stem && stem <= to_stem + 1 {
+                    return None;
+                }
+            }
+            Change::Fact(key, fact) => {
+                let (from, to) = fact.endpoints();
+                let visible = pages.element_in_pages(from)
+                    && pages.element_in_pages(to)
+                    && match fact {
+                        Fact::ExtTau { .. } => {
+                            include_tau && from_stem <= model.stem(from) && model.stem(from) <= to_stem
+                        }
+                        _ => true,
+                    };
+                if visible {
+                    added.push((key, fact));
+                }
+            }
+        }
+    }
+
+    if added.is_empty() {
+        return Some(cached);
+    }
+
+    let mut facts = (*cached.facts).clone();
+    facts.extend(added.iter().copied());
+    facts.sort_by_key(|(key, _)| *key);
+
+    // The generators whose state may change: everything connected to a new fact.
+    let mut neighbours: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (_, fact) in &facts {
+        let (from, to) = fact.endpoints();
+        neighbours.entry(from).or_default().push(to);
+        neighbours.entry(to).or_default().push(from);
+    }
+    let mut component = HashSet::new();
+    let mut stack: Vec<usize> = added
+        .iter()
+        .flat_map(|(_, fact)| {
+            let (from, to) = fact.endpoints();
+            [from, to]
+        })
+        .collect();
+    while let Some(id) = stack.pop() {
+        if component.insert(id) {
+            stack.extend(neighbours[&id].iter().copied());
+        }
+    }
+
+    let mut pages = (**pages).clone();
+    for &id in &component {
+        pages.generators[id] = Some(vec![(1, (model.af(id), data.generators()[id]))]);
+    }
+
+    // Both endpoints of a fact lie in the same component, so the issues of the
+    // other components stay as they were.
+    let mut issues: Vec<_> = cached
+        .issues
+        .iter()
+        .filter(|(_, fact, _)| !component.contains(&fact.endpoints().0))
+        .cloned()
+        .collect();
+    for (key, fact) in &facts {
+        if component.contains(&fact.endpoints().0)
+            && let Err(issue) = apply_fact(data, model, &mut pages, *fact)
+        {
+            issues.push((*key, *fact, issue));
+        }
+    }
+    issues.sort_by_key(|(key, _, _)| *key);
+
+    Some(WindowPages {
+        pages: Arc::new(pages),
+        facts: Arc::new(facts),
+        issues: Arc::new(issues),
+    })
+}
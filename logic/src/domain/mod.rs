@@ -1,13 +1,21 @@
 //! The core domain model of the synthetic spectral sequence.
 //!
 //! - [`e1`]: the E1 page — the fixed set of generators and lookup indices.
+//! - [`homotopy`]: homotopy groups of spheres assembled from computed pages.
+//! - [`incremental`]: caches computed pages per truncation and updates them
+//!   for newly added facts instead of recomputing from scratch.
 //! - [`model`]: [`model::SyntheticSS`], the user-asserted differentials and
 //!   tau-multiplications layered on top of an E1 page.
 //! - [`process`]: turns a `SyntheticSS` into computed [`ss::SSPages`] by applying
 //!   those facts page by page, reporting any [`crate::solve::issues::Issue`]s.
+//! - [`query`]: searching the E1 page by bidegree, partial tag and name, and
+//!   the fate of the matches in a truncation.
 //! - [`ss`]: [`ss::SSPages`], the per-generator state across pages after computation.
 
 pub mod e1;
+pub mod homotopy;
+pub mod incremental;
 pub mod model;
 pub mod process;
+pub mod query;
 pub mod ss;
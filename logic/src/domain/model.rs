@@ -1,16 +1,24 @@
 //! [`SyntheticSS`]: the spectral-sequence facts asserted on top of an [`E1`]
 //! page — differentials, internal tau-multiplications (same bidegree) and
-//! external tau-multiplications (same stem). All facts are keyed in `from_to`
+//! external tau-multiplications (same stem). Hidden 2-extensions only matter
+//! for assembling homotopy groups ([`crate::domain::homotopy`]), so they are
+//! kept apart in `two_extensions`. All facts are keyed in `from_to`
 //! to dedupe, and additionally bucketed (by page / y-degree) so that
 //! [`crate::domain::process`] can apply them in the right order.
+//!
+//! The per-generator and per-fact collections are persistent ([`im`]) so that
+//! cloning a sequence to explore a branch shares everything with its parent and
+//! only the facts the branch adds cost memory.
 
-use std::collections::HashMap;
-
+use im::{HashMap, OrdMap, Vector};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     MAX_STEM,
-    domain::e1::E1,
+    domain::{
+        e1::E1,
+        incremental::{Fact, Journal, PagesCache},
+    },
     types::{Kind, Torsion},
 };
 
@@ -39,31 +47,54 @@ pub struct ExtTauMult {
     pub af: i32,
 }
 
+/// A hidden 2-extension `2 * from = to` in homotopy, holding on `S^n` for every
+/// `n >= sphere`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TwoExtension {
+    pub from: usize,
+    pub to: usize,
+    pub sphere: i32,
+    pub proof: Option<String>,
+}
+
 // This should always implicitly reference some Model
+//
+// Everything that reaches the pages is private: it may only change through the
+// methods below, which record the change in the journal so cached pages are
+// invalidated.
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct SyntheticSS {
-    pub generators: Vec<Torsion>,
-    pub induced_name: Option<Vec<Vec<(i32, String)>>>,
+    generators: Vec<Torsion>,
+    pub induced_name: Option<Vector<Vec<(i32, String)>>>,
 
     // This should be indexed by page ??
     // Or should it be indexed by Gens
     // Length of this should equal max_stem + 1
-    pub diffs_page: Vec<Vec<Diff>>,
-    pub internal_tau_page: Vec<Vec<IntTauMult>>,
+    diffs_page: Vec<Vec<Diff>>,
+    internal_tau_page: Vec<Vec<IntTauMult>>,
 
     // This happens at the "final" page
-    // Indexed by the y coordinate of "from"
-    // 2nd index is for the y difference
-    // Third is by the AF thing (Meaning, the "better" the element fits onto the other the earlier it should be applied)
-    pub external_tau_page: Vec<Vec<Vec<Vec<ExtTauMult>>>>,
+    // Keyed by (y coordinate of "from", AF, y difference) and applied in key order
+    // AF: Meaning, the "better" the element fits onto the other the earlier it should be applied
+    external_tau_page: OrdMap<(i32, i32, i32), Vec<ExtTauMult>>,
 
-    pub from_to: HashMap<FromTo, (Kind, Option<String>)>,
+    from_to: HashMap<FromTo, (Kind, Option<String>)>,
 
     // Remember incoming/outgoing stuff
-    pub in_diffs: Vec<Vec<usize>>,
-    pub out_diffs: Vec<Vec<usize>>,
+    in_diffs: Vector<Vec<usize>>,
+    out_diffs: Vector<Vec<usize>>,
+
+    out_taus: Vector<Vec<usize>>,
+
+    #[serde(default)]
+    pub two_extensions: Vector<TwoExtension>,
 
-    pub out_taus: Vec<Vec<usize>>,
+    // Bookkeeping for incremental page computation, see `domain::incremental`.
+    // Every change to the facts above or to `generators` is recorded here.
+    #[serde(skip)]
+    pub(super) journal: Journal,
+    #[serde(skip)]
+    pub(super) pages_cache: PagesCache,
 }
 
 impl SyntheticSS {
@@ -74,17 +105,14 @@ impl SyntheticSS {
             induced_name: None,
             diffs_page: vec![vec![]; (MAX_STEM + 1) as usize],
             internal_tau_page: vec![vec![]; (MAX_STEM + 1) as usize],
-            external_tau_page: vec![
-                vec![
-                    vec![vec![]; (MAX_STEM + 1) as usize];
-                    (MAX_STEM + 1) as usize
-                ];
-                (MAX_STEM + 1) as usize
-            ],
+            external_tau_page: OrdMap::new(),
             from_to: HashMap::default(),
-            in_diffs: vec![vec![]; len],
-            out_diffs: vec![vec![]; len],
-            out_taus: vec![vec![]; len],
+            in_diffs: Vector::from(vec![vec![]; len]),
+            out_diffs: Vector::from(vec![vec![]; len]),
+            out_taus: Vector::from(vec![vec![]; len]),
+            two_extensions: Vector::new(),
+            journal: Journal::default(),
+            pages_cache: PagesCache::default(),
         }
     }
 
@@ -95,6 +123,8 @@ impl SyntheticSS {
             self.from_to.insert((from, to), (kind, proof));
             match kind {
                 Kind::Real | Kind::Algebraic => {
+                    let key = (0, d_y, 1, 0, self.diffs_page[d_y as usize].len());
+                    self.journal.record_fact(key, Fact::Diff { page: d_y, from, to });
                     self.diffs_page[d_y as usize].push(Diff { from, to });
                     self.in_diffs[to].push(from);
                     self.out_diffs[from].push(to);
@@ -116,6 +146,8 @@ impl SyntheticSS {
             self.from_to.insert((from, to), (kind, proof));
             match kind {
                 Kind::Real => {
+                    let key = (0, page, 0, 0, self.internal_tau_page[page as usize].len());
+                    self.journal.record_fact(key, Fact::IntTau { page, from, to });
                     self.internal_tau_page[page as usize].push(IntTauMult { from, to });
                 }
                 _ => {}
@@ -138,8 +170,10 @@ impl SyntheticSS {
                 Kind::Real => {
                     let y_from = model.y(from);
                     let y_to = model.y(to);
-                    self.external_tau_page[y_from as usize][af as usize][(y_from - y_to) as usize]
-                        .push(ExtTauMult { from, to, af });
+                    let bucket = self.external_tau_page.entry((y_from, af, y_from - y_to)).or_default();
+                    let key = (1, y_from, af, y_from - y_to, bucket.len());
+                    bucket.push(ExtTauMult { from, to, af });
+                    self.journal.record_fact(key, Fact::ExtTau { af, from, to });
                     self.out_taus[from].push(to);
                 }
                 _ => {}
@@ -193,10 +227,83 @@ impl SyntheticSS {
 
     pub fn set_generator(&mut self, model: &E1, name: &String, torsion: Torsion) -> Result<(), ()> {
         let id = model.try_index(name).ok_or(())?;
-        self.generators[id] = torsion;
+        self.set_torsion(id, torsion);
         Ok(())
     }
 
+    /// Set the E1 torsion of a generator. Always go through this rather than
+    /// writing to `generators`, so cached pages get invalidated.
+    pub fn set_torsion(&mut self, id: usize, torsion: Torsion) {
+        if self.generators[id] != torsion {
+            self.generators[id] = torsion;
+            self.journal.record_generator(id);
+        }
+    }
+
+
+    /// Append the data of a generator just pushed onto the model, τ-free and
+    /// without facts.
+    pub fn push_generator(&mut self) {
+        let id = self.generators.len();
+        self.generators.push(Torsion::default());
+        self.in_diffs.push_back(vec![]);
+        self.out_diffs.push_back(vec![]);
+        self.out_taus.push_back(vec![]);
+        self.journal.record_generator(id);
+    }
+
+    /// Forget a fact recorded as `Unknown`, so it can be asserted again. Such
+    /// facts never reach the pages, so nothing needs to be invalidated.
+    pub fn forget_unknown(&mut self, from_to: FromTo) {
+        if self.from_to.get(&from_to).is_some_and(|(kind, _)| *kind == Kind::Unknown) {
+            self.from_to.remove(&from_to);
+        }
+    }
+
+    /// The E1 torsion of every generator.
+    pub fn generators(&self) -> &[Torsion] {
+        &self.generators
+    }
+
+    /// The differentials by length, each page in the order they are applied.
+    pub fn diffs_page(&self) -> &[Vec<Diff>] {
+        &self.diffs_page
+    }
+
+    /// The internal taus by page, each page in the order they are applied.
+    pub fn internal_tau_page(&self) -> &[Vec<IntTauMult>] {
+        &self.internal_tau_page
+    }
+
+    /// The external taus bucketed by (y of the source, AF, y difference).
+    pub fn external_tau_page(&self) -> &OrdMap<(i32, i32, i32), Vec<ExtTauMult>> {
+        &self.external_tau_page
+    }
+
+    /// The kind and proof of every asserted fact, Fake and Unknown included.
+    pub fn facts(&self) -> &HashMap<FromTo, (Kind, Option<String>)> {
+        &self.from_to
+    }
+
+    /// For each generator, the sources of the differentials into it.
+    pub fn in_diffs(&self) -> &Vector<Vec<usize>> {
+        &self.in_diffs
+    }
+
+    /// For each generator, the targets of the differentials out of it.
+    pub fn out_diffs(&self) -> &Vector<Vec<usize>> {
+        &self.out_diffs
+    }
+
+    /// For each generator, the targets of the external taus out of it.
+    pub fn out_taus(&self) -> &Vector<Vec<usize>> {
+        &self.out_taus
+    }
+
+    /// All external taus, in the order they are applied.
+    pub fn ext_taus(&self) -> impl Iterator<Item = &ExtTauMult> {
+        self.external_tau_page.values().flatten()
+    }
 
     pub fn get_name_at_sphere<'a>(&'a self, model: &'a E1, elt: usize, sphere: i32) -> &'a str {
         let l: &Vec<(i32, String)> = if let Some(v) = &self.induced_name
@@ -220,10 +327,20 @@ impl SyntheticSS {
         }
     }
 
+    pub fn add_two_extension(&mut self, from: usize, to: usize, sphere: i32, proof: Option<String>) {
+        if !self
+            .two_extensions
+            .iter()
+            .any(|e| e.from == from && e.to == to && e.sphere == sphere)
+        {
+            self.two_extensions.push_back(TwoExtension { from, to, sphere, proof });
+        }
+    }
+
     pub fn push_induced_name(&mut self, model: &E1, elt: usize, sphere: i32, new_name: String) {
         let len = model.gens().len();
         if self.induced_name.is_none() {
-            self.induced_name = Some(vec![vec![]; len]);
+            self.induced_name = Some(Vector::from(vec![vec![]; len]));
         }
         let map = self.induced_name.as_mut().unwrap();
         if map[elt].is_empty() {
@@ -3,14 +3,22 @@
 //! The flow is: [`instantiate_pages`] seeds every generator with its E1 state,
 //! then [`apply_diff`] and [`apply_tau`] mutate that state page by page as the
 //! asserted differentials and tau-multiplications are applied. [`compute_pages`]
-//! drives the whole thing; anything inconsistent is reported as an [`Issue`]
+//! drives the whole thing, reusing earlier results through
+//! [`crate::domain::incremental`]; anything inconsistent is reported as an [`Issue`]
 //! rather than panicking. [`ehp_recursion`] is the EHP-specific step that lifts
 //! one sphere's computed values up to the next.
 
+use std::sync::Arc;
+
 use crate::{
     MAX_STEM,
     data::naming::{add_sphere_to_tag, generating_tag},
-    domain::{e1::E1, model::SyntheticSS, ss::SSPages},
+    domain::{
+        e1::E1,
+        incremental::{Fact, FactKey, Window, WindowPages, cached_window},
+        model::SyntheticSS,
+        ss::SSPages,
+    },
     solve::issues::Issue,
     types::{Kind, Torsion},
 };
@@ -30,7 +38,7 @@ fn instantiate_pages(
 
     let mut max_stem = 0;
 
-    for (index, torsion) in data.generators.iter().enumerate() {
+    for (index, torsion) in data.generators().iter().enumerate() {
         let g = model.get(index);
         max_stem = max_stem.max(g.stem);
 
@@ -75,7 +83,7 @@ fn apply_diff(
         // Source alive but target already dead: a real differential here has
         // nothing to hit (useless); an algebraic one is just expected, so no-op.
         if !to_g.1.alive() {
-            if data.from_to[&(from, to)].0 != Kind::Algebraic {
+            if data.facts()[&(from, to)].0 != Kind::Algebraic {
                 let (from_name, to_name) = model.get_names(from, to);
                 return Err(Issue::UselessDifferential {
                     from,
@@ -144,7 +152,7 @@ fn apply_diff(
                         }
                     }
                 } else {
-                    if data.from_to[&(from, to)].0 != Kind::Algebraic {
+                    if data.facts()[&(from, to)].0 != Kind::Algebraic {
                         // Useless
                         let (from_name, to_name) = model.get_names(from, to);
                         return Err(Issue::UselessDifferential {
@@ -186,7 +194,7 @@ fn apply_diff(
     } else {
         // Source already dead: a real differential out of it is useless; if it
         // was algebraic (or the target is also dead) just keep the states as-is.
-        if to_g.1.alive() && data.from_to[&(from, to)].0 != Kind::Algebraic {
+        if to_g.1.alive() && data.facts()[&(from, to)].0 != Kind::Algebraic {
             let (from_name, to_name) = model.get_names(from, to);
             return Err(Issue::UselessDifferential {
                 from,
@@ -273,7 +281,7 @@ pub fn try_compute_pages(
     from_stem: i32,
     to_stem: i32,
     include_tau: bool,
-) -> Result<SSPages, Vec<Issue>> {
+) -> Result<Arc<SSPages>, Vec<Issue>> {
     let (pages, issues) =
         compute_pages(data, model, bot_trunc, top_trunc, from_stem, to_stem, include_tau);
 
@@ -285,70 +293,85 @@ pub fn try_compute_pages(
 }
 
 
-pub fn compute_diffs_int_taus(
-    data: &SyntheticSS,
-    model: &E1,
-    bot_trunc: i32,
-    top_trunc: i32,
-    from_stem: i32,
-    to_stem: i32,
-) -> (SSPages, Vec<Issue>) {
-    let mut pages = instantiate_pages(data, model, bot_trunc, top_trunc, from_stem, to_stem);
+/// Every fact of `data` that takes part in computing `window`, in the order it
+/// is applied: per page the internal taus and then the differentials, and after
+/// all pages the external taus (only those starting in `from_stem..=to_stem`,
+/// and only if `include_tau`). A fact is only applied if both endpoints are in
+/// the truncation.
+pub fn window_facts(data: &SyntheticSS, model: &E1, pages: &SSPages, window: Window) -> Vec<(FactKey, Fact)> {
+    let (_, _, from_stem, to_stem, include_tau) = window;
+    let visible = |from: usize, to: usize| pages.element_in_pages(from) && pages.element_in_pages(to);
 
-    let mut issues = vec![];
+    let mut facts = vec![];
 
     for page in 0..=MAX_STEM as usize {
-        for t in &data.internal_tau_page[page] {
-            if pages.element_in_pages(t.from) && pages.element_in_pages(t.to) {
-                if let Err(i) = apply_tau(model, &mut pages, page as i32, 0, t.from, t.to) {
-                    issues.push(i);
-                }
+        for (index, t) in data.internal_tau_page()[page].iter().enumerate() {
+            if visible(t.from, t.to) {
+                let fact = Fact::IntTau { page: page as i32, from: t.from, to: t.to };
+                facts.push(((0, page as i32, 0, 0, index), fact));
             }
         }
 
-        for d in &data.diffs_page[page] {
-            if pages.element_in_pages(d.from) && pages.element_in_pages(d.to) {
-                if let Err(i) = apply_diff(data, model, &mut pages, page as i32, d.from, d.to) {
-                    issues.push(i);
+        for (index, d) in data.diffs_page()[page].iter().enumerate() {
+            if visible(d.from, d.to) {
+                let fact = Fact::Diff { page: page as i32, from: d.from, to: d.to };
+                facts.push(((0, page as i32, 1, 0, index), fact));
+            }
+        }
+    }
+
+    if include_tau {
+        // `from` has to be in the truncation, which bounds its y.
+        let rows = (pages.bot_trunc, i32::MIN, i32::MIN)..=(pages.top_trunc, i32::MAX, i32::MAX);
+        for (&(y, af, dy), es) in data.external_tau_page().range(rows) {
+            for (index, e) in es.iter().enumerate() {
+                if visible(e.from, e.to)
+                    && from_stem <= model.stem(e.from)
+                    && model.stem(e.from) <= to_stem
+                {
+                    let fact = Fact::ExtTau { af: e.af, from: e.from, to: e.to };
+                    facts.push(((1, y, af, dy, index), fact));
                 }
             }
         }
     }
 
-    (pages, issues)
+    facts
 }
 
-pub fn compute_ext_taus(
-    pages: &mut SSPages,
-    model: &E1,
-    data: &SyntheticSS,
-    _bot_trunc: i32,
-    _top_trunc: i32,
-    from_stem: i32,
-    to_stem: i32,
-) -> Vec<Issue> {
+/// Apply a single fact to the pages.
+pub fn apply_fact(data: &SyntheticSS, model: &E1, pages: &mut SSPages, fact: Fact) -> Result<(), Issue> {
+    match fact {
+        Fact::IntTau { page, from, to } => apply_tau(model, pages, page, 0, from, to),
+        Fact::Diff { page, from, to } => apply_diff(data, model, pages, page, from, to),
+        Fact::ExtTau { af, from, to } => apply_tau(model, pages, 500, af, from, to),
+    }
+}
+
+/// Compute the pages for `window` from scratch.
+pub fn compute_window(data: &SyntheticSS, model: &E1, window: Window) -> WindowPages {
+    let (bot_trunc, top_trunc, from_stem, to_stem, _) = window;
+    let mut pages = instantiate_pages(data, model, bot_trunc, top_trunc, from_stem, to_stem);
+
+    let facts = window_facts(data, model, &pages, window);
+
     let mut issues = vec![];
-    for esss in &data.external_tau_page {
-        for ess in esss {
-            for es in ess {
-                for e in es {
-                    if pages.element_in_pages(e.from)
-                        && pages.element_in_pages(e.to)
-                        && from_stem <= model.stem(e.from)
-                        && model.stem(e.from) <= to_stem
-                    {
-                        if let Err(i) = apply_tau(model, pages, 500, e.af, e.from, e.to) {
-                            issues.push(i);
-                        }
-                    }
-                }
-            }
+    for (key, fact) in &facts {
+        if let Err(i) = apply_fact(data, model, &mut pages, *fact) {
+            issues.push((*key, *fact, i));
         }
     }
 
-    issues
+    WindowPages {
+        pages: Arc::new(pages),
+        facts: Arc::new(facts),
+        issues: Arc::new(issues),
+    }
 }
 
+/// The pages of `data` in the given truncation, together with every issue found
+/// while computing them. Pages computed before on (an ancestor of) `data` are
+/// updated incrementally, see [`crate::domain::incremental`].
 pub fn compute_pages(
     data: &SyntheticSS,
     model: &E1,
@@ -357,17 +380,11 @@ pub fn compute_pages(
     from_stem: i32,
     to_stem: i32,
     include_tau: bool,
-) -> (SSPages, Vec<Issue>) {
-
-
-    let (mut pages, mut issues) = compute_diffs_int_taus(data, model, bot_trunc, top_trunc, from_stem, to_stem);
-
-    if include_tau {
-        let mut issues2 = compute_ext_taus(&mut pages, model, data, bot_trunc, top_trunc, from_stem, to_stem);
-        issues.append(&mut issues2);
-    }
+) -> (Arc<SSPages>, Vec<Issue>) {
+    let window = cached_window(data, model, (bot_trunc, top_trunc, from_stem, to_stem, include_tau));
 
-    (pages, issues)
+    let issues = window.issues.iter().map(|(_, _, i)| i.clone()).collect();
+    (window.pages.clone(), issues)
 }
 
 /// One step of the EHP recursion: using the sequence computed up through
@@ -386,7 +403,7 @@ pub fn ehp_recursion(ehp: &mut SyntheticSS, model: &E1, sphere: i32, stem: i32)
     .gens_id_in_stem_y(stem + sphere / 2, sphere / 2)
     .clone()
     {
-        ehp.generators[id] = Torsion::zero();
+        ehp.set_torsion(id, Torsion::zero());
     }
 
     let mut issues = vec![];
@@ -415,7 +432,7 @@ pub fn ehp_recursion(ehp: &mut SyntheticSS, model: &E1, sphere: i32, stem: i32)
                             to_name: target_name,
                         });
                     } else {
-                        ehp.generators[target_id] = g.1;
+                        ehp.set_torsion(target_id, g.1);
                     }
                 }
                 None => {
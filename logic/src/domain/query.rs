@@ -0,0 +1,230 @@
+//! Searching the E1 page for generators when only part of a name, or just the
+//! bidegree, is known. A [`GeneratorQuery`] filters on stem, y, AF, tag prefix
+//! or suffix, the algebraic born/dies spheres and loosely on a name (canonical,
+//! Ext or Toda); [`search`] ranks the matches and [`Fate`] says what becomes of
+//! a match in a given truncation.
+
+use std::{fmt, ops::RangeInclusive};
+
+use crate::{
+    data::{names::NAMES, naming::name_get_tag},
+    domain::{e1::E1, model::SyntheticSS, ss::SSPages},
+    types::Torsion,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GeneratorQuery {
+    pub stem: Option<i32>,
+    pub y: Option<i32>,
+    pub af: Option<i32>,
+    /// The tag starts with these lambda letters, e.g. `"6 5"`.
+    pub prefix: Option<String>,
+    /// The tag ends with these lambda letters, e.g. `"3 3"`.
+    pub suffix: Option<String>,
+    pub born: Option<RangeInclusive<i32>>,
+    /// `Some(None)` asks for generators that never die.
+    pub dies: Option<Option<RangeInclusive<i32>>>,
+    /// Any part of the canonical, Ext or Toda name, or a name
+    /// [`E1::resolve`] accepts.
+    pub name: Option<String>,
+}
+
+impl GeneratorQuery {
+    /// Parse comma separated `key=value` filters, e.g.
+    /// `"stem=14, af=4, suffix=3 3"`. Keys are `stem`, `y`, `af`, `prefix`,
+    /// `suffix`, `born`, `dies` and `name`; `born` and `dies` take a sphere or
+    /// a range `a..b`, `a..=b`, `a..` or `..b` read as in Rust, so `3..5`
+    /// means spheres 3 and 4, and `dies=never` matches generators
+    /// that survive to the stable range.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut q = GeneratorQuery::default();
+        for filter in s.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+            let Some((key, value)) = filter.split_once('=') else {
+                return Err(format!("Expected key=value, found {filter}"));
+            };
+            let (key, value) = (key.trim(), value.trim());
+            let int = || value.parse::<i32>().map_err(|_| format!("{key} needs a number, found {value}"));
+            match key {
+                "stem" => q.stem = Some(int()?),
+                "y" => q.y = Some(int()?),
+                "af" => q.af = Some(int()?),
+                "prefix" => q.prefix = Some(words(value)),
+                "suffix" => q.suffix = Some(words(value)),
+                "born" => q.born = Some(parse_range(value)?),
+                "dies" if value == "never" => q.dies = Some(None),
+                "dies" => q.dies = Some(Some(parse_range(value)?)),
+                "name" => q.name = Some(value.to_string()),
+                _ => return Err(format!("Unknown filter {key}")),
+            }
+        }
+        Ok(q)
+    }
+
+    fn matches(&self, model: &E1, id: usize) -> bool {
+        let g = model.get(id);
+        let tag = name_get_tag(&g.name);
+        self.stem.is_none_or(|s| g.stem == s)
+            && self.y.is_none_or(|y| g.y == y)
+            && self.af.is_none_or(|af| g.af == af)
+            && self.prefix.as_ref().is_none_or(|p| starts_with_words(tag, p))
+            && self.suffix.as_ref().is_none_or(|p| ends_with_words(tag, p))
+            && self.born.as_ref().is_none_or(|r| r.contains(&g.born))
+            && self.dies.as_ref().is_none_or(|r| match (r, g.dies) {
+                (None, dies) => dies.is_none(),
+                (Some(r), Some(dies)) => r.contains(&dies),
+                (Some(_), None) => false,
+            })
+    }
+
+    /// How well `id` matches the `name` filter, lower is better, `None` if it
+    /// does not match at all: 0 if the name resolves to it, 1 if one of its
+    /// names starts with the search, 2 if one contains it.
+    fn name_rank(&self, model: &E1, id: usize) -> Option<u8> {
+        let Some(search) = &self.name else {
+            return Some(0);
+        };
+        if model.resolve(search) == Ok(id) {
+            return Some(0);
+        }
+        let name = model.name(id);
+        let names = [
+            Some(name.to_string()),
+            NAMES.alg_name(name),
+            NAMES.hom_name(name, false),
+            NAMES.hom_name(name, true),
+        ];
+        let names = names.iter().flatten();
+        if names.clone().any(|n| n.starts_with(search.as_str())) {
+            Some(1)
+        } else if names.clone().any(|n| n.contains(search.as_str())) {
+            Some(2)
+        } else {
+            None
+        }
+    }
+}
+
+/// The generators matching `query`, best first: by how well the name matches,
+/// then by tag length (the simplest class in a bidegree first), then by stem,
+/// y and AF. Uses the stem/AF and stem/y indices when the query pins them.
+pub fn search(model: &E1, query: &GeneratorQuery) -> Vec<usize> {
+    let candidates: Vec<usize> = match (query.stem, query.af, query.y) {
+        (Some(stem), _, Some(y)) => model.gens_id_in_stem_y(stem, y).clone(),
+        (Some(stem), Some(af), None) => model.gens_id_in_stem_af(stem, af).clone(),
+        (Some(stem), None, None) => model.gens_id_in_stem(stem).clone(),
+        (None, _, _) => (0..model.gens().len()).collect(),
+    };
+
+    let mut ranked: Vec<_> = candidates
+        .into_iter()
+        .filter(|&id| query.matches(model, id))
+        .filter_map(|id| {
+            let g = model.get(id);
+            let tag_len = name_get_tag(&g.name).split_whitespace().count();
+            Some(((query.name_rank(model, id)?, tag_len, g.stem, g.y, g.af), id))
+        })
+        .collect();
+    ranked.sort();
+    ranked.into_iter().map(|(_, id)| id).collect()
+}
+
+/// What becomes of a generator in one truncation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fate {
+    /// The generator is not in the truncation.
+    Absent,
+    /// Survives to E∞ in AF `af` (different from its own after an external
+    /// tau), with the given torsion.
+    Survives { af: i32, torsion: Torsion },
+    /// Dead from page `page` on.
+    Dies { page: i32 },
+}
+
+impl Fate {
+    pub fn of(pages: &SSPages, id: usize) -> Self {
+        let Some(states) = &pages.generators[id] else {
+            return Fate::Absent;
+        };
+        match states.iter().find(|(_, (_, t))| !t.alive()) {
+            Some((page, _)) => Fate::Dies { page: *page },
+            None => {
+                let (af, torsion) = states.last().unwrap().1;
+                Fate::Survives { af, torsion }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Fate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fate::Absent => write!(f, "not in this truncation"),
+            Fate::Survives { af, torsion } => write!(f, "survives in AF {af}, {}", torsion_string(*torsion)),
+            Fate::Dies { page } => write!(f, "dies on E{page}"),
+        }
+    }
+}
+
+fn torsion_string(torsion: Torsion) -> String {
+    match torsion.0 {
+        None => "τ-free".to_string(),
+        Some(0) => "dead".to_string(),
+        Some(n) => format!("τ^{n}-torsion"),
+    }
+}
+
+/// One line per generator: name, bidegree, Ext name, Toda name (if it
+/// survives τ-free), E1 torsion in `data` and fate in `pages`.
+pub fn describe(data: &SyntheticSS, model: &E1, pages: &SSPages, id: usize, ahss: bool) -> String {
+    let g = model.get(id);
+    let fate = Fate::of(pages, id);
+    let hom_name = match fate {
+        Fate::Survives { torsion, .. } if torsion.free() => NAMES.hom_name(&g.name, ahss),
+        _ => None,
+    };
+    let names: Vec<String> = [NAMES.alg_name(&g.name), hom_name]
+        .into_iter()
+        .flatten()
+        .filter(|n| n != &g.name)
+        .collect();
+    format!(
+        "{:<28} stem {:>2} y {:>2} AF {:>2}  {:<20} E1 {:<14} {}",
+        g.name,
+        g.stem,
+        g.y,
+        g.af,
+        names.join(" "),
+        torsion_string(data.generators()[id]),
+        fate
+    )
+}
+
+fn words(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn starts_with_words(tag: &str, prefix: &str) -> bool {
+    let (tag, prefix): (Vec<_>, Vec<_>) = (tag.split_whitespace().collect(), prefix.split_whitespace().collect());
+    tag.starts_with(&prefix)
+}
+
+fn ends_with_words(tag: &str, suffix: &str) -> bool {
+    let (tag, suffix): (Vec<_>, Vec<_>) = (tag.split_whitespace().collect(), suffix.split_whitespace().collect());
+    tag.ends_with(&suffix)
+}
+
+fn parse_range(s: &str) -> Result<RangeInclusive<i32>, String> {
+    let int = |x: &str| x.trim().parse::<i32>().map_err(|_| format!("Invalid range {s}"));
+    let bound = |x: &str, default: i32| if x.trim().is_empty() { Ok(default) } else { int(x) };
+    match s.split_once("..") {
+        Some((a, b)) => match b.strip_prefix('=') {
+            Some(b) => Ok(bound(a, i32::MIN)?..=int(b)?),
+            None if b.trim().is_empty() => Ok(bound(a, i32::MIN)?..=i32::MAX),
+            None => Ok(bound(a, i32::MIN)?..=int(b)?.saturating_sub(1)),
+        },
+        None => {
+            let n = int(s)?;
+            Ok(n..=n)
+        }
+    }
+}
@@ -0,0 +1,154 @@
+//! Layout of a single page of a truncation, shared by the static chart
+//! renderers (`tikz`, `svg`): which classes are alive on the page, where they
+//! are drawn, and which differentials and taus connect them.
+//!
+//! Classes sit at (stem, AF) as they are on the page, spread out horizontally
+//! when a bidegree has several. The edges of page `r` are its differentials,
+//! the internal taus of pages up to `r`, and on the E∞ page (any page from
+//! [`E_INFINITY`] on) the external taus.
+
+use std::collections::HashMap;
+
+use crate::{
+    MAX_STEM,
+    domain::{e1::E1, model::SyntheticSS, ss::SSPages},
+    types::{Kind, Torsion},
+};
+
+/// The page external taus are applied on, see `domain::process`.
+pub const E_INFINITY: i32 = 500;
+
+/// Page and torsion colors, cycling like the website's torsion colors.
+const COLORS: [&str; 6] = ["black", "blue", "red", "teal", "cyan", "violet"];
+
+/// Horizontal distance between classes in the same bidegree.
+const SPREAD: f32 = 0.18;
+
+pub fn page_color(page: i32) -> &'static str {
+    COLORS[page.rem_euclid(COLORS.len() as i32) as usize]
+}
+
+pub fn torsion_color(torsion: Torsion) -> &'static str {
+    COLORS[torsion.0.unwrap_or(0) as usize % COLORS.len()]
+}
+
+pub struct ChartClass {
+    pub id: usize,
+    pub x: f32,
+    pub af: i32,
+    pub torsion: Torsion,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeType {
+    Differential,
+    InternalTau,
+    ExternalTau,
+}
+
+pub struct ChartEdge {
+    pub from: usize,
+    pub to: usize,
+    pub edge_type: EdgeType,
+    pub page: i32,
+    pub kind: Kind,
+}
+
+pub struct ChartPage {
+    pub page: i32,
+    pub max_af: i32,
+    /// Sorted by generator index.
+    pub classes: Vec<ChartClass>,
+    pub edges: Vec<ChartEdge>,
+    /// Drawing position of every class in `classes`.
+    pub position: HashMap<usize, (f32, i32)>,
+}
+
+/// Lay out `page` of `pages`, which were computed from `data`.
+pub fn layout_page(data: &SyntheticSS, model: &E1, pages: &SSPages, page: i32) -> ChartPage {
+    let mut classes = vec![];
+    let mut per_bidegree: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+    for (id, g) in model.enumerate() {
+        if g.stem > MAX_STEM || !pages.element_in_pages(id) {
+            continue;
+        }
+        let (af, torsion) = pages.element_at_page(page, id);
+        if torsion.alive() {
+            per_bidegree.entry((g.stem, af)).or_default().push(classes.len());
+            classes.push(ChartClass { id, x: g.stem as f32, af, torsion });
+        }
+    }
+
+    for indices in per_bidegree.values() {
+        let shift = (indices.len() - 1) as f32 / 2.0;
+        for (i, index) in indices.iter().enumerate() {
+            classes[*index].x += (i as f32 - shift) * SPREAD;
+        }
+    }
+    let position: HashMap<_, _> = classes.iter().map(|c| (c.id, (c.x, c.af))).collect();
+    let max_af = classes.iter().map(|c| c.af).max().unwrap_or(0);
+
+    let mut edges = vec![];
+    let mut edge = |from: usize, to: usize, edge_type, page| {
+        if position.contains_key(&from) && position.contains_key(&to) {
+            let kind = data.facts().get(&(from, to)).map_or(Kind::Real, |(k, _)| *k);
+            edges.push(ChartEdge { from, to, edge_type, page, kind });
+        }
+    };
+
+    if let Some(diffs) = data.diffs_page().get(page as usize) {
+        for d in diffs {
+            edge(d.from, d.to, EdgeType::Differential, page);
+        }
+    }
+    for (tau_page, taus) in data.internal_tau_page().iter().enumerate() {
+        if tau_page as i32 > page {
+            break;
+        }
+        for t in taus {
+            edge(t.from, t.to, EdgeType::InternalTau, tau_page as i32);
+        }
+    }
+    if page >= E_INFINITY {
+        for e in data.ext_taus() {
+            edge(e.from, e.to, EdgeType::ExternalTau, page);
+        }
+    }
+
+    ChartPage {
+        page,
+        max_af,
+        classes,
+        edges,
+        position,
+    }
+}
+
+/// The pages worth drawing for `pages`: every page from the first to the last
+/// one with a differential in the truncation, and E∞.
+pub fn chart_pages(data: &SyntheticSS, pages: &SSPages) -> Vec<i32> {
+    let with_diffs: Vec<i32> = data
+        .diffs_page()
+        .iter()
+        .enumerate()
+        .filter(|(_, ds)| {
+            ds.iter()
+                .any(|d| pages.element_in_pages(d.from) && pages.element_in_pages(d.to))
+        })
+        .map(|(page, _)| page as i32)
+        .collect();
+
+    match (with_diffs.first(), with_diffs.last()) {
+        (Some(&first), Some(&last)) => (first..=last).chain([E_INFINITY]).collect(),
+        _ => vec![E_INFINITY],
+    }
+}
+
+/// `E2`, `E3`, ..., `Einf`, as used in file names.
+pub fn page_file_name(page: i32) -> String {
+    if page >= E_INFINITY {
+        "Einf".to_string()
+    } else {
+        format!("E{page}")
+    }
+}
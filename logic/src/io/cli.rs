@@ -1,14 +1,25 @@
 //! Interactive terminal menu used to enter spectral-sequence facts by hand
-//! (differentials, tau-multiplications, generators, induced names) and to revert
-//! previous choices. Each menu selection is translated into an [`Action`].
+//! (differentials, tau-multiplications, 2-extensions, generators, induced names) and to revert
+//! previous choices. Each menu selection is translated into an [`Action`];
+//! the menu can also search the E1 page ([`print_search`]) without leaving it.
 
 use std::io::{self, Write};
 
 use crate::{
+    MAX_STEM,
+    domain::{
+        e1::E1,
+        model::SyntheticSS,
+        process::compute_pages,
+        query::{GeneratorQuery, describe, search},
+    },
     solve::action::Action,
     types::{Kind, Torsion},
 };
 
+/// How many search results are printed.
+const SEARCH_RESULTS: usize = 40;
+
 fn read_line() -> String {
     let mut input = String::new();
     io::stdin().read_line(&mut input).expect("Failed to read");
@@ -49,7 +60,29 @@ fn prompt_int(label: &str) -> i32 {
     read_int(true)
 }
 
-pub fn process_input(ahss: bool) -> Result<Action, ()> {
+/// Search the E1 page with the filters of [`GeneratorQuery::parse`] and print
+/// the matches with their fate in the truncation `bot_trunc..=top_trunc`.
+pub fn print_search(data: &SyntheticSS, model: &E1, ahss: bool, bot_trunc: i32, top_trunc: i32, query: &str) {
+    let query = match GeneratorQuery::parse(query) {
+        Ok(query) => query,
+        Err(e) => {
+            println!("{e}");
+            return;
+        }
+    };
+    let ids = search(model, &query);
+    let (pages, _) = compute_pages(data, model, bot_trunc, top_trunc, 0, MAX_STEM, true);
+    for &id in ids.iter().take(SEARCH_RESULTS) {
+        println!("{}", describe(data, model, &pages, id, ahss));
+    }
+    match ids.len() {
+        0 => println!("No generator matches"),
+        n if n > SEARCH_RESULTS => println!("... and {} more", n - SEARCH_RESULTS),
+        _ => {}
+    }
+}
+
+pub fn process_input(ahss: bool, data: &SyntheticSS, model: &E1) -> Result<Action, ()> {
     loop {
         if ahss {
             println!("Select AHSS option:");
@@ -64,9 +97,11 @@ pub fn process_input(ahss: bool) -> Result<Action, ()> {
         }
         if !ahss {
             println!("5 - Set induced name");
+            println!("6 - Add 2-extension");
         }
         println!("7 - Revert Previous Choice");
         println!("8 - Revert Previous n Choices");
+        println!("9 - Search Generators");
         if ahss {
             println!("0 - Continue to EHP");
         } else {
@@ -139,6 +174,18 @@ pub fn process_input(ahss: bool) -> Result<Action, ()> {
                     proof: prompt_line("Proof"),
                 });
             }
+            6 => {
+                if ahss {
+                    println!("Cannot add 2-extensions in AHSS mode");
+                    continue;
+                }
+                return Ok(Action::AddTwoExt {
+                    from: prompt_line("From"),
+                    to: prompt_line("To (2 * From)"),
+                    sphere: prompt_int("From Sphere"),
+                    proof: Some(prompt_line("Proof")),
+                });
+            }
             7 => {
                 return Ok(Action::Revert { times: 1 });
             }
@@ -148,6 +195,17 @@ pub fn process_input(ahss: bool) -> Result<Action, ()> {
                 });
             }
 
+            9 => {
+                let (bot_trunc, top_trunc) = if ahss {
+                    (prompt_int("Bottom cell"), prompt_int("Top cell"))
+                } else {
+                    (0, prompt_int("Sphere") - 1)
+                };
+                let query = prompt_line("Filters (e.g. stem=14, af=4, suffix=3 3, name=κ)");
+                print_search(data, model, ahss, bot_trunc, top_trunc, &query);
+                println!();
+            }
+
             _ => {
                 println!("Unknown option.");
             }
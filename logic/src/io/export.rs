@@ -1,16 +1,18 @@
-//! Serialization of the computed spectral sequence to disk: the website's
-//! TypeScript data files (`write_typescript_file`), the replayable action logs
-//! (`write_log`), and a LaTeX-style order table (`export_order_table`).
+//! Serialization of the computed spectral sequence to disk: a versioned JSON
+//! export (`export_ss`/`write_json_file`, schema in `schema/` at the repo root),
+//! the website's TypeScript data files wrapping it (`write_typescript_file`),
+//! the replayable action logs (`write_log`), and a table of the homotopy groups
+//! of spheres as CSV, Markdown or LaTeX (`export_homotopy_table`).
 
 use std::{
-    fs::File, io::{self, Write}, path::{Path, PathBuf}
+    fs::{self, File}, io::{self, BufWriter, Write}, path::{Path, PathBuf}
 };
 
-use itertools::Itertools;
+use itertools::{Itertools, chain};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    MAX_STEM, data::curtis::MODEL, domain::{e1::E1, model::SyntheticSS, process::compute_pages}, solve::action::Action, types::{Generator, Kind, Torsion}
+    MAX_STEM, MAX_VERIFY_STEM, data::{curtis::MODEL, names::NAMES}, domain::{e1::E1, homotopy::{ComputedGroup, homotopy_group}, model::SyntheticSS, process::compute_pages, ss::PagesGeneratorState}, solve::action::Action, types::{Generator, Kind}
 };
 
 pub fn write_vec_to_file<T: std::fmt::Debug>(vec: &[T], path: &str) -> io::Result<()> {
@@ -76,42 +78,85 @@ pub struct Multiplication {
     pub kind: String,
 }
 
-pub fn write_typescript_file(
-    output_path: &str,
-    data_name: &str,
-    data: &SyntheticSS,
-    model: &E1,
-) -> Result<(), std::io::Error> {
-    let mut file = File::create(output_path)?;
+/// Version of the JSON format written by [`write_json_file`], described by
+/// `schema/synthetic_ss.schema.json` in the repo root. Bump both together.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The states of the generators that are in the pages of one truncation, as
+/// `(generator index, states)` pairs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TruncationPages {
+    pub bot_trunc: i32,
+    pub top_trunc: i32,
+    pub generators: Vec<(usize, PagesGeneratorState)>,
+}
+
+/// Everything we export of a spectral sequence: the E1 generators, all facts,
+/// and optionally the computed pages of every sphere (EHP) or `RP_1^n` (AHSS).
+/// The website computes its own pages, so its copy leaves them out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedSS {
+    pub schema_version: u32,
+    pub max_stem: i32,
+    pub generators: Vec<Generator>,
+    pub differentials: Vec<Differential>,
+    pub multiplications: Vec<Multiplication>,
+    pub internal_tau_mults: Vec<InternalTauMult>,
+    pub external_tau_mults: Vec<ExternalTauMult>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub truncations: Vec<TruncationPages>,
+}
+
+pub fn export_ss(data: &SyntheticSS, model: &E1, ahss: bool, with_pages: bool) -> ExportedSS {
+    let truncations = (1..=MAX_STEM)
+        .filter(|_| with_pages)
+        .map(|n| {
+            let (bot_trunc, top_trunc) = if ahss { (1, n) } else { (0, n - 1) };
+            let (pages, _) = compute_pages(data, model, bot_trunc, top_trunc, 0, MAX_STEM, true);
+            let generators = pages
+                .generators
+                .iter()
+                .enumerate()
+                .filter_map(|(id, states)| Some((id, states.clone()?)))
+                .collect();
+            TruncationPages {
+                bot_trunc,
+                top_trunc,
+                generators,
+            }
+        })
+        .collect();
+
+    // Toda names only go to classes that detect an element: tau-free at E∞
+    // of the untruncated sequence.
+    let bot_trunc = if ahss { 1 } else { 0 };
+    let (full, _) = compute_pages(data, model, bot_trunc, 256, 0, MAX_STEM, true);
 
     let mut data = data.clone();
-    // add_final_diagonal would mutate the model too; skipped under new design
     let mut model = model.clone();
 
-    for (index, torsion) in data.generators.iter().enumerate() {
-        model.get_mut(index).torsion = *torsion;
+    for (index, torsion) in data.generators().iter().enumerate() {
+        let g = model.get_mut(index);
+        g.torsion = *torsion;
+        g.alg_name = NAMES.alg_name(&g.name);
+        if full.try_element_final(index).is_some_and(|(_, t)| t.free()) {
+            g.hom_name = NAMES.hom_name(&g.name, ahss);
+        }
     }
 
     add_final_diagonal(&mut model, &mut data);
 
-    // Serialize generators to JSON strings
-    let gens: Vec<String> = model
-        .gens()
-        .iter()
-        .map(|g| serde_json::to_string(g).unwrap())
-        .collect();
-
     // Differentials, internal and external tau-multiplications are all stored
     // together in `from_to`; we recover which is which from the bidegree shift.
     let mut differentials = vec![];
-    let mut int_tau_mults = vec![];
-    let mut ext_tau_mults = vec![];
+    let mut internal_tau_mults = vec![];
+    let mut external_tau_mults = vec![];
 
-    for ((from, to), (kind, p)) in &data.from_to {
+    for ((from, to), (kind, p)) in data.facts() {
         let d_y = model.y(*from) - model.y(*to);
         let d_stem = model.stem(*from) - model.stem(*to);
         if d_y == 0 {
-            int_tau_mults.push(InternalTauMult {
+            internal_tau_mults.push(InternalTauMult {
                 from: model.name(*from).to_string(),
                 to: model.name(*to).to_string(),
                 kind: *kind,
@@ -119,7 +164,7 @@ pub fn write_typescript_file(
                 page: 2, // TODO : This is not generic enough but good enough for our range
             });
         } else if d_stem == 0 {
-            ext_tau_mults.push(ExternalTauMult {
+            external_tau_mults.push(ExternalTauMult {
                 from: model.name(*from).to_string(),
                 to: model.name(*to).to_string(),
                 af: 0,
@@ -137,67 +182,59 @@ pub fn write_typescript_file(
         }
     }
 
-    // Serialize differentials to JSON strings
-    let diffs: Vec<String> = differentials
-        .iter()
-        .map(|d| serde_json::to_string(d).unwrap())
-        .collect();
-
-    let int_tau_mults: Vec<String> = int_tau_mults
-        .iter()
-        .map(|m| serde_json::to_string(m).unwrap())
-        .collect();
-
-    let ext_tau_mults: Vec<String> = ext_tau_mults
-        .iter()
-        .map(|m| serde_json::to_string(m).unwrap())
-        .collect();
+    ExportedSS {
+        schema_version: SCHEMA_VERSION,
+        max_stem: MAX_STEM,
+        generators: model.gens().to_vec(),
+        differentials,
+        multiplications: vec![],
+        internal_tau_mults,
+        external_tau_mults,
+        truncations,
+    }
+}
 
-    let mults: Vec<String> = vec![];
+pub fn write_json_file(output_path: &Path, export: &ExportedSS) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(output_path)?);
+    serde_json::to_writer(&mut file, export)?;
+    file.flush()
+}
 
-    let pre = format!(
+/// The TypeScript module the website imports: a typed re-export of the JSON
+/// file `json_name` next to it.
+pub fn write_typescript_file(output_path: &Path, data_name: &str, json_name: &str) -> io::Result<()> {
+    let ts = format!(
         "// @ts-nocheck\n\
-         // This file has been generated by curtis.rs\n\
-         // Based on curtis tables of the AEHP sequence\n\
-         import {{ SyntheticEHP }} from \"./types\";\n\n\
-         export const MAX_STEM{} = {};\n\n\
-         export const data{}: SyntheticEHP = {{\n\
-         \x20   \"generators\": [\n",
+         // This file has been generated by export.rs\n\
+         // The data itself is in {json_name}, see schema/synthetic_ss.schema.json\n\
+         import {{ SyntheticEHP }} from \"./types\";\n\
+         import json from \"./{json_name}\";\n\n\
+         export const MAX_STEM{} = json.max_stem;\n\n\
+         export const data{}: SyntheticEHP = json;\n",
         data_name.to_uppercase(),
-        MAX_STEM,
         data_name
     );
-
-    let ds = "\n    ],\n    \"differentials\": [\n";
-    let ms = "\n    ],\n    \"multiplications\": [\n";
-    let its = "\n    ],\n    \"internal_tau_mults\": [\n";
-    let ets = "\n    ],\n    \"external_tau_mults\": [\n";
-    let post = "    ]\n}";
-
-    file.write_all(pre.as_bytes())?;
-    file.write_all(gens.join(",\n").as_bytes())?;
-    file.write_all(ds.as_bytes())?;
-    file.write_all(diffs.join(",\n").as_bytes())?;
-    file.write_all(ms.as_bytes())?;
-    file.write_all(mults.join(",\n").as_bytes())?;
-    file.write_all(its.as_bytes())?;
-    file.write_all(int_tau_mults.join(",\n").as_bytes())?;
-    file.write_all(ets.as_bytes())?;
-    file.write_all(ext_tau_mults.join(",\n").as_bytes())?;
-    file.write_all(post.as_bytes())?;
-
-    Ok(())
+    fs::write(output_path, ts)
 }
 
 pub fn write_all(data: &SyntheticSS, model: &E1, log: &Vec<Action>, ahss: bool) {
     let log = log.iter().unique().map(|x| x.clone()).collect();
-    if ahss {
-        write_typescript_file("../site/src/data_stable.ts", "_stable", &data, model).unwrap();
-        write_log(&log, ahss).unwrap();
-    } else {
-        write_typescript_file("../site/src/data.ts", "", &data, model).unwrap();
-        write_log(&log, ahss).unwrap();
-    }
+    let data_name = if ahss { "_stable" } else { "" };
+    let json_name = format!("data{data_name}.json");
+
+    let export = export_ss(data, model, ahss, false);
+    write_json_file(&repo_root_path(&format!("site/src/{json_name}")), &export).unwrap();
+    write_typescript_file(&repo_root_path(&format!("site/src/data{data_name}.ts")), data_name, &json_name).unwrap();
+    write_log(&log, ahss).unwrap();
+}
+
+/// Write the full export, pages included, to `export/{ehp,ahss}.json` in the
+/// repo root, for tools other than the website.
+pub fn write_full_export(data: &SyntheticSS, model: &E1, ahss: bool) -> io::Result<()> {
+    let dir = repo_root_path("export");
+    fs::create_dir_all(&dir)?;
+    let name = if ahss { "ahss" } else { "ehp" };
+    write_json_file(&dir.join(format!("{name}.json")), &export_ss(data, model, ahss, true))
 }
 
 pub fn write_log(log: &Vec<Action>, ahss: bool) -> io::Result<()> {
@@ -211,43 +248,85 @@ pub fn write_log(log: &Vec<Action>, ahss: bool) -> io::Result<()> {
 }
 
 
-// TODO : Fix again
-pub fn export_order_table(ehp: &SyntheticSS) {
-    let mut things = vec![vec![]; (MAX_STEM + 1) as usize];
-
-    things[0].push("".to_string());
+/// Output format of [`export_homotopy_table`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableFormat {
+    Csv,
+    Markdown,
+    Latex,
+}
 
-    for s in 1..MAX_STEM {
-        things[0].push(format!("$S^{{{s}}}$"));
+impl TableFormat {
+    /// The format belonging to the extension of `path` (`csv`, `md` or `tex`).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "csv" => Some(Self::Csv),
+            "md" => Some(Self::Markdown),
+            "tex" => Some(Self::Latex),
+            _ => None,
+        }
     }
+}
 
-    for stem in 0..MAX_STEM {
-        let line_init = format!("$\\pi_{{{stem}+n}}(S^n)$");
-        things[(stem + 1) as usize].push(line_init);
+/// The highest sphere in the homotopy table: from here on every verified stem
+/// is in the stable range.
+const TABLE_MAX_SPHERE: i32 = MAX_VERIFY_STEM + 2;
+
+/// π_{n+k}(S^n)_(2) for every stem k (rows) up to `MAX_VERIFY_STEM` and every
+/// sphere n (columns) up to the stable range. Groups whose 2-extensions the
+/// computation does not determine are written as an unknown extension of
+/// their order.
+pub fn homotopy_table(ehp: &SyntheticSS) -> Vec<Vec<ComputedGroup>> {
+    let mut table = vec![vec![]; (MAX_VERIFY_STEM + 1) as usize];
+    for sphere in 1..=TABLE_MAX_SPHERE {
+        let (pages, _) = compute_pages(ehp, &MODEL, 0, sphere - 1, 0, MAX_STEM, true);
+        for stem in 0..=MAX_VERIFY_STEM {
+            table[stem as usize].push(homotopy_group(ehp, &MODEL, &pages, sphere, stem));
+        }
     }
+    table
+}
 
-    for sphere in 1..MAX_STEM {
-        let top_trunc = sphere - 1;
-        let (pages, _) = compute_pages(ehp, &MODEL, 0, top_trunc, 0, MAX_STEM, true);
-
-        for stem in 0..MAX_STEM {
-            let mut count = 0;
-            for id in MODEL.gens_id_in_stem(stem) {
-                if let Some(el) = pages.try_element_final(*id)
-                    && el.1.free()
-                {
-                    count += 1;
-                }
+/// Write [`homotopy_table`] to `path` as CSV, a Markdown table or a LaTeX
+/// `tabular`.
+pub fn export_homotopy_table(ehp: &SyntheticSS, path: &Path, format: TableFormat) -> io::Result<()> {
+    let table = homotopy_table(ehp);
+    let spheres = 1..=TABLE_MAX_SPHERE;
+
+    let mut out = String::new();
+    match format {
+        TableFormat::Csv => {
+            out.push_str(&chain!(["k".to_string()], spheres.map(|n| format!("S^{n}"))).join(","));
+            out.push('\n');
+            for (stem, row) in table.iter().enumerate() {
+                out.push_str(&chain!([stem.to_string()], row.iter().map(|g| g.to_string())).join(","));
+                out.push('\n');
             }
-
-            things[(stem + 1) as usize].push(format!("{}", count));
+        }
+        TableFormat::Markdown => {
+            out.push_str(&format!("| k | {} |\n", spheres.clone().map(|n| format!("S^{n}")).join(" | ")));
+            out.push_str(&format!("|---|{}\n", spheres.map(|_| "---|").join("")));
+            for (stem, row) in table.iter().enumerate() {
+                out.push_str(&format!("| {stem} | {} |\n", row.iter().join(" | ")));
+            }
+        }
+        TableFormat::Latex => {
+            out.push_str(&format!("\\begin{{tabular}}{{r|{}}}\n", "c".repeat(table[0].len())));
+            out.push_str(&format!(
+                " & {} \\\\\n\\hline\n",
+                spheres.map(|n| format!("$S^{{{n}}}$")).join(" & ")
+            ));
+            for (stem, row) in table.iter().enumerate() {
+                out.push_str(&format!(
+                    "$\\pi_{{{stem}+n}}(S^n)$ & {} \\\\\n",
+                    row.iter().map(|g| format!("${}$", g.latex())).join(" & ")
+                ));
+            }
+            out.push_str("\\end{tabular}\n");
         }
     }
 
-    let joined: Vec<_> = things.into_iter().map(|x| x.join(",")).collect();
-    for l in joined {
-        println!("{l}");
-    }
+    fs::write(path, out)
 }
 
 
@@ -262,18 +341,9 @@ fn add_final_diagonal(model: &mut E1, data: &mut SyntheticSS ) {
         model.push(Generator::new(from_name.clone(), n + 1, n + 1, 1, 0, None));
         model.push(Generator::new(to_name.clone(), n, n, 2, 0, None));
         
-        data.generators.push(Torsion::default());
-        data.generators.push(Torsion::default());
+        data.push_generator();
+        data.push_generator();
 
-        data.in_diffs.push(vec![]);
-        data.in_diffs.push(vec![]);
-        
-        data.out_diffs.push(vec![]);
-        data.out_diffs.push(vec![]);
-        
-        data.out_taus.push(vec![]);
-        data.out_taus.push(vec![]);
-        
         data.add_diff(model, size, size+1, None, Kind::Algebraic);
     }
 }
@@ -1,7 +1,13 @@
 //! Input/output: the interactive terminal menu (`cli`), serialization of the
 //! computed spectral sequence to the website's TypeScript data files (`export`),
-//! and loading saved action logs back in (`import`).
+//! charts in the sseq JSON format (`sseq`), as TikZ pictures (`tikz`) and as
+//! SVGs (`svg`, both laid out by `chart`), and loading saved action logs back
+//! in (`import`).
 
+pub mod chart;
 pub mod cli;
 pub mod export;
-pub mod import;
\ No newline at end of file
+pub mod import;
+pub mod sseq;
+pub mod svg;
+pub mod tikz;
\ No newline at end of file
@@ -0,0 +1,197 @@
+//! Export of a computed truncation to the `SseqChart` JSON format of the
+//! [SpectralSequences](https://github.com/SpectralSequences/sseq) chart library,
+//! so our charts can be loaded in the standard viewers next to other Adams charts.
+//!
+//! Classes sit at (stem, AF) of their E1 generator and live until the page they
+//! die on. Real and algebraic differentials become `ChartDifferential`s on their
+//! page, internal taus become structure lines from their page on, and external
+//! taus become `ChartExtension`s.
+
+use std::{collections::HashMap, fs, io};
+
+use serde::Serialize;
+
+use crate::{
+    MAX_STEM,
+    domain::{e1::E1, model::SyntheticSS, process::compute_pages},
+    io::export::repo_root_path,
+};
+
+/// The library's stand-in for the infinite page.
+const INFINITY: i32 = 65535;
+
+/// A value that can change from page to page: `(first page, value)` pairs.
+#[derive(Debug, Clone, Serialize)]
+struct PageProperty<T> {
+    r#type: &'static str,
+    values: Vec<(i32, T)>,
+}
+
+impl<T> PageProperty<T> {
+    fn constant(value: T) -> Self {
+        Self {
+            r#type: "PageProperty",
+            values: vec![(0, value)],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ChartClass {
+    r#type: &'static str,
+    uuid: String,
+    degree: [i32; 2],
+    idx: usize,
+    name: PageProperty<String>,
+    max_page: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ChartEdge {
+    r#type: &'static str,
+    uuid: String,
+    source_uuid: String,
+    target_uuid: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    page: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    page_min: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SseqChart {
+    r#type: &'static str,
+    name: String,
+    uuid: String,
+    page_list: Vec<[i32; 2]>,
+    initial_x_range: [i32; 2],
+    initial_y_range: [i32; 2],
+    x_range: [i32; 2],
+    y_range: [i32; 2],
+    num_gradings: usize,
+    x_projection: [i32; 2],
+    y_projection: [i32; 2],
+    classes: Vec<ChartClass>,
+    edges: Vec<ChartEdge>,
+}
+
+fn class_uuid(id: usize) -> String {
+    format!("class-{id}")
+}
+
+/// Build the chart of the truncation `bot_trunc..=top_trunc` of `data`.
+fn sseq_chart(name: &str, data: &SyntheticSS, model: &E1, bot_trunc: i32, top_trunc: i32) -> SseqChart {
+    let (pages, _) = compute_pages(data, model, bot_trunc, top_trunc, 0, MAX_STEM, true);
+
+    let mut classes = vec![];
+    let mut max_y = 0;
+    // Generators are stored in stem order, so the index within a bidegree is
+    // the number of classes we have seen there before.
+    let mut seen = HashMap::new();
+    for (id, g) in model.enumerate() {
+        let Some(states) = &pages.generators[id] else {
+            continue;
+        };
+        if g.stem > MAX_STEM {
+            continue;
+        }
+
+        // A class is last visible on the page before the one it is dead on.
+        let max_page = states
+            .iter()
+            .find(|(_, (_, torsion))| !torsion.alive())
+            .map_or(INFINITY, |(page, _)| page - 1);
+
+        let idx = seen.entry((g.stem, g.af)).or_insert(0);
+        classes.push(ChartClass {
+            r#type: "ChartClass",
+            uuid: class_uuid(id),
+            degree: [g.stem, g.af],
+            idx: *idx,
+            name: PageProperty::constant(g.name.clone()),
+            max_page,
+        });
+        *idx += 1;
+        max_y = max_y.max(g.af);
+    }
+
+    let visible = |from: usize, to: usize| pages.element_in_pages(from) && pages.element_in_pages(to);
+    let mut edges = vec![];
+    let mut edge = |r#type, from, to, page, page_min| {
+        edges.push(ChartEdge {
+            r#type,
+            uuid: format!("edge-{}", edges.len()),
+            source_uuid: class_uuid(from),
+            target_uuid: class_uuid(to),
+            page,
+            page_min,
+        });
+    };
+
+    let (mut min_page, mut max_page) = (2, 2);
+    for (page, ds) in data.diffs_page().iter().enumerate() {
+        for d in ds.iter().filter(|d| visible(d.from, d.to)) {
+            edge("ChartDifferential", d.from, d.to, Some(page as i32), None);
+            min_page = min_page.min(page as i32);
+            max_page = max_page.max(page as i32);
+        }
+    }
+    for (page, ts) in data.internal_tau_page().iter().enumerate() {
+        for t in ts.iter().filter(|t| visible(t.from, t.to)) {
+            edge("ChartStructline", t.from, t.to, None, Some(page as i32));
+        }
+    }
+    for e in data.ext_taus().filter(|e| visible(e.from, e.to)) {
+        edge("ChartExtension", e.from, e.to, None, None);
+    }
+
+    let page_list = (min_page..=max_page)
+        .map(|page| [page, page])
+        .chain([[INFINITY, INFINITY]])
+        .collect();
+
+    SseqChart {
+        r#type: "SseqChart",
+        name: name.to_string(),
+        uuid: name.to_string(),
+        page_list,
+        initial_x_range: [0, MAX_STEM],
+        initial_y_range: [0, max_y],
+        x_range: [0, MAX_STEM],
+        y_range: [0, max_y],
+        num_gradings: 2,
+        x_projection: [1, 0],
+        y_projection: [0, 1],
+        classes,
+        edges,
+    }
+}
+
+/// Write the chart of the truncation `bot_trunc..=top_trunc` of `data` to
+/// `sseq/{name}.json` in the repo root.
+pub fn write_sseq_chart(
+    name: &str,
+    data: &SyntheticSS,
+    model: &E1,
+    bot_trunc: i32,
+    top_trunc: i32,
+) -> io::Result<()> {
+    let chart = sseq_chart(name, data, model, bot_trunc, top_trunc);
+
+    let dir = repo_root_path("sseq");
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join(format!("{name}.json")), serde_json::to_string(&chart)?)
+}
+
+/// Write the sseq charts of a whole sequence: RP_1^∞ for the AHSS, every sphere
+/// and the stable range for the EHP.
+pub fn write_sseq_charts(data: &SyntheticSS, model: &E1, ahss: bool) -> io::Result<()> {
+    if ahss {
+        write_sseq_chart("ahss_RP1_inf", data, model, 1, 256)
+    } else {
+        for sphere in 1..=MAX_STEM {
+            write_sseq_chart(&format!("ehp_S{sphere}"), data, model, 0, sphere - 1)?;
+        }
+        write_sseq_chart("ehp_stable", data, model, 0, 256)
+    }
+}
@@ -0,0 +1,178 @@
+//! Static SVG charts, rendered without the website so charts can be produced
+//! in batch (e.g. from the `svg` command or next to a solver run).
+//! [`write_svg_charts`] writes one SVG per page of a truncation to `svg/` in
+//! the repo root, as laid out by [`crate::io::chart`].
+//!
+//! The drawing follows the website: classes are dots colored by torsion (with
+//! τ^n-torsion drawn as a tower of n dots going down in AF), differentials are
+//! colored by page and dashed by their [`Kind`], and τ-extensions are gray.
+
+use std::{
+    fmt::Write as _,
+    fs, io,
+    path::PathBuf,
+};
+
+use crate::{
+    MAX_STEM,
+    domain::{e1::E1, model::SyntheticSS, process::compute_pages},
+    io::{
+        chart::{ChartPage, E_INFINITY, EdgeType, chart_pages, layout_page, page_color, page_file_name, torsion_color},
+        export::repo_root_path,
+    },
+    types::Kind,
+};
+
+/// Pixels per unit of stem / AF.
+const UNIT: f32 = 24.0;
+/// Space around the grid for the axis labels and the title.
+const MARGIN: f32 = 30.0;
+const DOT_RADIUS: f32 = 2.5;
+
+fn kind_dasharray(kind: Kind) -> &'static str {
+    match kind {
+        Kind::Real => "none",
+        Kind::Algebraic => "1 2",
+        Kind::Fake => "5 3",
+        Kind::Unknown => "5 2 1 2",
+        Kind::Invisible | Kind::Unnecessary | Kind::MinimalLength | Kind::AdditiveStructure => "1 4",
+    }
+}
+
+/// Render a laid out page as an SVG document.
+fn svg_page(model: &E1, chart: &ChartPage) -> String {
+    let max_af = chart.max_af.max(1);
+    let width = MAX_STEM as f32 * UNIT + 2.0 * MARGIN;
+    let height = max_af as f32 * UNIT + 2.0 * MARGIN;
+    // SVG has y pointing down, charts have AF pointing up.
+    let px = |x: f32| MARGIN + x * UNIT;
+    let py = |af: i32| MARGIN + (max_af - af) as f32 * UNIT;
+
+    let title = if chart.page >= E_INFINITY {
+        "E∞".to_string()
+    } else {
+        format!("E{}", chart.page)
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\" font-family=\"monospace\" font-size=\"9\">"
+    );
+    let _ = writeln!(out, "<!-- This file has been generated by svg.rs -->");
+    let _ = writeln!(out, "<rect width=\"100%\" height=\"100%\" fill=\"white\"/>");
+    let _ = writeln!(
+        out,
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-size=\"14\">{title}</text>",
+        width / 2.0,
+        MARGIN / 2.0
+    );
+
+    for stem in 0..=MAX_STEM {
+        let _ = writeln!(
+            out,
+            "<line x1=\"{x}\" y1=\"{}\" x2=\"{x}\" y2=\"{}\" stroke=\"#eee\"/>",
+            py(max_af),
+            py(0),
+            x = px(stem as f32)
+        );
+        if stem % 2 == 0 {
+            let _ = writeln!(
+                out,
+                "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{stem}</text>",
+                px(stem as f32),
+                py(0) + 14.0
+            );
+        }
+    }
+    for af in 0..=max_af {
+        let _ = writeln!(
+            out,
+            "<line x1=\"{}\" y1=\"{y}\" x2=\"{}\" y2=\"{y}\" stroke=\"#eee\"/>",
+            px(0.0),
+            px(MAX_STEM as f32),
+            y = py(af)
+        );
+        if af % 2 == 0 {
+            let _ = writeln!(
+                out,
+                "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{af}</text>",
+                px(0.0) - 6.0,
+                py(af) + 3.0
+            );
+        }
+    }
+
+    for edge in &chart.edges {
+        let (x1, y1) = chart.position[&edge.from];
+        let (x2, y2) = chart.position[&edge.to];
+        let color = match edge.edge_type {
+            EdgeType::Differential | EdgeType::InternalTau => page_color(edge.page),
+            EdgeType::ExternalTau => "gray",
+        };
+        let _ = writeln!(
+            out,
+            "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"{color}\" stroke-width=\"0.8\" stroke-dasharray=\"{}\"><title>{} → {}</title></line>",
+            px(x1),
+            py(y1),
+            px(x2),
+            py(y2),
+            kind_dasharray(edge.kind),
+            model.name(edge.from),
+            model.name(edge.to)
+        );
+    }
+
+    for class in &chart.classes {
+        let color = torsion_color(class.torsion);
+        let x = px(class.x);
+        if let Some(n) = class.torsion.0
+            && n > 1
+        {
+            let _ = writeln!(
+                out,
+                "<line x1=\"{x:.1}\" y1=\"{}\" x2=\"{x:.1}\" y2=\"{}\" stroke=\"{color}\"/>",
+                py(class.af),
+                py(class.af - n + 1)
+            );
+            for i in 1..n {
+                let _ = writeln!(
+                    out,
+                    "<circle cx=\"{x:.1}\" cy=\"{}\" r=\"{DOT_RADIUS}\" fill=\"{color}\"/>",
+                    py(class.af - i)
+                );
+            }
+        }
+        let _ = writeln!(
+            out,
+            "<circle cx=\"{x:.1}\" cy=\"{}\" r=\"{DOT_RADIUS}\" fill=\"{color}\"><title>{}</title></circle>",
+            py(class.af),
+            model.name(class.id)
+        );
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Write an SVG for every page of the truncation `bot_trunc..=top_trunc` of
+/// `data` to `svg/{name}_E{page}.svg` in the repo root, returning the paths.
+pub fn write_svg_charts(
+    name: &str,
+    data: &SyntheticSS,
+    model: &E1,
+    bot_trunc: i32,
+    top_trunc: i32,
+) -> io::Result<Vec<PathBuf>> {
+    let (pages, _) = compute_pages(data, model, bot_trunc, top_trunc, 0, MAX_STEM, true);
+
+    let dir = repo_root_path("svg");
+    fs::create_dir_all(&dir)?;
+    let mut paths = vec![];
+    for page in chart_pages(data, &pages) {
+        let path = dir.join(format!("{name}_{}.svg", page_file_name(page)));
+        fs::write(&path, svg_page(model, &layout_page(data, model, &pages, page)))?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
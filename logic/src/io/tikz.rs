@@ -0,0 +1,120 @@
+//! Export of a single page of a truncation as a standalone TikZ picture, for
+//! redrawing charts in papers. [`write_tikz_sphere`] draws the EHP truncation
+//! of a sphere and [`write_tikz_rp`] an RP truncation of the AHSS, both to
+//! `tikz/` in the repo root.
+//!
+//! The page is laid out by [`crate::io::chart`]. A τ^n-torsion class is drawn
+//! as a tower of n dots going down in AF (τ-free classes are a single black
+//! dot). Differentials and internal taus are colored by page and styled by
+//! their [`Kind`]; external taus only appear on the E∞ page.
+
+use std::{fmt::Write as _, fs, io};
+
+use crate::{
+    MAX_STEM,
+    domain::{e1::E1, model::SyntheticSS, process::compute_pages},
+    io::{
+        chart::{ChartPage, E_INFINITY, EdgeType, layout_page, page_color, page_file_name, torsion_color},
+        export::repo_root_path,
+    },
+    types::Kind,
+};
+
+fn kind_style(kind: Kind) -> &'static str {
+    match kind {
+        Kind::Real => "solid",
+        Kind::Algebraic => "densely dotted",
+        Kind::Fake => "dashed",
+        Kind::Unknown => "dash dot",
+        Kind::Invisible | Kind::Unnecessary | Kind::MinimalLength | Kind::AdditiveStructure => {
+            "loosely dotted"
+        }
+    }
+}
+
+/// Draw a laid out page as a TikZ document.
+fn tikz_page(model: &E1, chart: &ChartPage) -> String {
+    let max_af = chart.max_af;
+    let title = if chart.page >= E_INFINITY {
+        "$E_\\infty$".to_string()
+    } else {
+        format!("$E_{{{}}}$", chart.page)
+    };
+
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "% This file has been generated by tikz.rs\n\
+         \\documentclass[tikz, border=5pt]{{standalone}}\n\
+         \\begin{{document}}\n\
+         \\begin{{tikzpicture}}[x=0.6cm, y=0.6cm, class/.style={{circle, fill, inner sep=0pt, minimum size=2.5pt}}]\n\
+         \\draw[help lines, gray!30] (0, 0) grid ({MAX_STEM}, {max_af});\n\
+         \\foreach \\x in {{0, 2, ..., {MAX_STEM}}} \\node[below] at (\\x, 0) {{\\tiny \\x}};\n\
+         \\foreach \\y in {{0, 2, ..., {max_af}}} \\node[left] at (0, \\y) {{\\tiny \\y}};\n\
+         \\node[above] at ({}, {}) {{{title}}};\n",
+        MAX_STEM as f32 / 2.0,
+        max_af as f32 + 0.5,
+    );
+
+    for class in &chart.classes {
+        let (x, af) = (class.x, class.af);
+        let color = torsion_color(class.torsion);
+        let _ = writeln!(out, "\\node[class, {color}] at ({x:.2}, {af}) {{}}; % {}", model.name(class.id));
+        if let Some(n) = class.torsion.0 {
+            // The tower x, τx, ..., τ^{n-1}x.
+            if n > 1 {
+                let _ = writeln!(out, "\\draw[{color}] ({x:.2}, {af}) -- ({x:.2}, {});", af - n + 1);
+            }
+            for i in 1..n {
+                let _ = writeln!(out, "\\node[class, {color}] at ({x:.2}, {}) {{}};", af - i);
+            }
+        }
+    }
+
+    for edge in &chart.edges {
+        let (x1, y1) = chart.position[&edge.from];
+        let (x2, y2) = chart.position[&edge.to];
+        let style = match edge.edge_type {
+            EdgeType::Differential => format!("->, {}, {}", page_color(edge.page), kind_style(edge.kind)),
+            EdgeType::InternalTau => format!("{}, {}", page_color(edge.page), kind_style(edge.kind)),
+            EdgeType::ExternalTau => format!("->, gray, bend left=20, {}", kind_style(edge.kind)),
+        };
+        let _ = writeln!(
+            out,
+            "\\draw[{style}] ({x1:.2}, {y1}) to ({x2:.2}, {y2}); % {} -> {}",
+            model.name(edge.from),
+            model.name(edge.to)
+        );
+    }
+
+    out.push_str("\\end{tikzpicture}\n\\end{document}\n");
+    out
+}
+
+/// Draw `page` of the truncation `bot_trunc..=top_trunc` of `data` to
+/// `tikz/{name}_E{page}.tex` in the repo root.
+pub fn write_tikz_chart(
+    name: &str,
+    data: &SyntheticSS,
+    model: &E1,
+    bot_trunc: i32,
+    top_trunc: i32,
+    page: i32,
+) -> io::Result<()> {
+    let (pages, _) = compute_pages(data, model, bot_trunc, top_trunc, 0, MAX_STEM, true);
+    let tex = tikz_page(model, &layout_page(data, model, &pages, page));
+
+    let dir = repo_root_path("tikz");
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join(format!("{name}_{}.tex", page_file_name(page))), tex)
+}
+
+/// Draw `page` of the EHP truncation of `S^sphere`, i.e. `0..=sphere-1`.
+pub fn write_tikz_sphere(data: &SyntheticSS, model: &E1, sphere: i32, page: i32) -> io::Result<()> {
+    write_tikz_chart(&format!("ehp_S{sphere}"), data, model, 0, sphere - 1, page)
+}
+
+/// Draw `page` of the AHSS of `RP_bot_trunc^top_trunc`.
+pub fn write_tikz_rp(data: &SyntheticSS, model: &E1, bot_trunc: i32, top_trunc: i32, page: i32) -> io::Result<()> {
+    write_tikz_chart(&format!("ahss_RP{bot_trunc}_{top_trunc}"), data, model, bot_trunc, top_trunc, page)
+}
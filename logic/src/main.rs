@@ -1,15 +1,31 @@
 //! Binary entry point. Wires together the data, domain, io, and solve modules.
 //! `main` is a scratch harness: the currently-active path replays the EHP log
-//! and prints the order table, while the alternative interactive/automated
-//! routines are toggled in and out during development.
+//! and writes the homotopy group table, while the alternative interactive/automated
+//! routines are toggled in and out during development. The `svg` command
+//! (`logic svg ...`) renders charts, the `table` command (`logic table
+//! <file>`) writes the homotopy groups of spheres and the `verify` command
+//! (`logic verify [report.json]`) checks them, exiting non-zero on a mismatch,
+//! the `search` command (`logic search sphere <n> <filters>`) looks up
+//! generators, the `cw` command (`logic cw <spectrum.json>`) solves and checks
+//! the AHSS of a CW spectrum described in a file, the `resolve` command
+//! (`logic resolve rp <bot> <top> <max_stem>`) computes comparison data by a
+//! minimal resolution and the `bockstein` command (`logic bockstein
+//! [max_stem]`) cross-checks that of the sphere. The `constraint` command
+//! (`logic constraint ahss|ehp <stem> [top_trunc]`) reports which undecided
+//! facts of a stem are forced and the `enumerate` command (`logic enumerate
+//! ahss|ehp <stem> [limit]`) lists every consistent configuration of them.
+//! The `sseq` and `json` commands (`logic sseq ahss|ehp`, `logic json
+//! ahss|ehp`) write the sseq charts and the full JSON export, and the `tikz`
+//! command (`logic tikz sphere <n> <page>` or `logic tikz rp <bot> <top>
+//! <page>`) a single page as TikZ. None of them run the harness.
 //!
 //! `MAX_STEM`/`MAX_VERIFY_STEM` bound the range of validity of the Curtis data.
 
 #[allow(unused)]
 use crate::{
-    data::curtis::{DATA, MODEL}, io::{
-        export::export_order_table, import::get_log,
-    }, routines::{automated_ahss, automated_ehp, interactive_ahss, interactive_ehp}, solve::{action::revert_log_and_remake, ehp::verify_geometric}
+    data::{classical::ClassicalTable, curtis::{DATA, MODEL}}, io::{
+        export::{TableFormat, export_homotopy_table, repo_root_path}, import::get_log,
+    }, routines::{automated_ahss, automated_ehp, bockstein_command, constraint_command, cw_command, enumerate_command, resolve_command, json_command, interactive_ahss, interactive_ehp, search_command, sseq_command, svg_command, table_command, tikz_command, verify_command}, solve::{action::revert_log_and_remake, ehp::verify_geometric}
 };
 
 mod data;
@@ -18,6 +34,8 @@ mod io;
 mod solve;
 mod types;
 mod routines;
+#[cfg(test)]
+mod tests;
 
 // AHSS CURTIS DATA IS VALID UNTIL STEM 48
 // EHP curtis data is also valid until STEM 48
@@ -48,6 +66,27 @@ const MAX_VERIFY_STEM: i32 = 47;
 
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let rest = args.get(1..).unwrap_or_default();
+    match args.first().map(String::as_str) {
+        Some("svg") => svg_command(rest),
+        Some("table") => table_command(rest),
+        Some("verify") => verify_command(rest),
+        Some("search") => search_command(rest),
+        Some("cw") => cw_command(rest),
+        Some("resolve") => resolve_command(rest),
+        Some("bockstein") => bockstein_command(rest),
+        Some("constraint") => constraint_command(rest),
+        Some("enumerate") => enumerate_command(rest),
+        Some("sseq") => sseq_command(rest),
+        Some("json") => json_command(rest),
+        Some("tikz") => tikz_command(rest),
+        _ => run_harness(),
+    }
+}
+
+/// The development harness run without a command, see the module docs.
+fn run_harness() {
     if 1 != 1 {
         interactive_ahss();
         interactive_ehp();
@@ -56,8 +95,8 @@ fn main() {
 
         let ehp = automated_ehp(true);
 
-        verify_geometric(&ehp);
-        export_order_table(&ehp);
+        verify_geometric(&ehp, &ClassicalTable::read(&ClassicalTable::default_path()).unwrap()).print_mismatches();
+
     }
 
     // let (ahss, _) = interactive_ahss();
@@ -65,9 +104,25 @@ fn main() {
     // interactive_ehp();
 
     // automated_ahss(true);
-    
+
+    let table = match ClassicalTable::read(&ClassicalTable::default_path()) {
+        Ok(table) => table,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(2);
+        }
+    };
+
     let ehp = automated_ehp(true);
-    
-    verify_geometric(&ehp);
-    export_order_table(&ehp);
+
+    let report = verify_geometric(&ehp, &table);
+    report.print_mismatches();
+    let path = repo_root_path("homotopy_groups.md");
+    if let Err(e) = export_homotopy_table(&ehp, &path, TableFormat::Markdown) {
+        eprintln!("Could not write {}: {e}", path.display());
+        std::process::exit(1);
+    }
+    if !report.passed() {
+        std::process::exit(1);
+    }
 }
@@ -3,15 +3,33 @@
 //! (auto-deducing what they can, prompting for the rest), while the
 //! `automated_*` routines run the same loop fully unattended via the solvers.
 //! Both replay a saved log first, so a session resumes where it left off.
+//! [`constraint_command`] instead reports which undecided facts of a single stem
+//! are forced by the reference data and which stay ambiguous, and
+//! [`enumerate_command`] lists every consistent world for a stem's `Unknown` facts.
+//! [`sseq_command`] writes the replayed sequence as sseq charts, and
+//! [`json_command`] as the full JSON export. [`tikz_command`]
+//! draws a single page of a truncation as TikZ, and [`svg_command`] renders
+//! every page of some truncations as SVG from the command line.
+//! [`table_command`] writes the table of homotopy groups of spheres, and
+//! [`verify_command`] checks them against the classical ones for scripts/CI.
+//! [`resolve_command`] computes the comparison data of a truncation with the
+//! built-in minimal resolution, and [`bockstein_command`] cross-checks that of
+//! the sphere at both ends of its τ-Bockstein spectral sequence.
+
+use std::{
+    fs,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
-use std::time::{Duration, Instant};
+use itertools::Itertools;
 
 use crate::{
-    MAX_STEM, MAX_VERIFY_STEM, data::curtis::{DATA, MODEL, STABLE_DATA, STABLE_MODEL}, domain::model::SyntheticSS, io::{
-        cli::process_input,
-        export::write_all, import::get_log,
+    MAX_STEM, MAX_VERIFY_STEM, data::{classical::ClassicalTable, cw::CwSpectrum, resolution::Resolution, r#static::{RP, S0, S0_ZEROES, resolved_compare_data}, steenrod::ProjectiveModule, curtis::{DATA, MODEL, STABLE_DATA, STABLE_MODEL}}, domain::model::SyntheticSS, io::{
+        cli::{print_search, process_input},
+        export::{TableFormat, export_homotopy_table, write_all, write_full_export}, import::get_log, sseq::write_sseq_charts, chart::E_INFINITY, svg::write_svg_charts, tikz::{write_tikz_rp, write_tikz_sphere},
     }, solve::{
-        action::{Action, process_action, revert_log_and_remake}, ahss::find_ahss_issues, automated_ahss::ahss_solver, automated_ehp::ehp_solver, ehp::{apply_ehp_recursively, find_ehp_issues}, ehp_ahss::{ehp_to_ahss_map, set_metastable_range}, solve::auto_deduce
+        action::{Action, process_action, revert_log_and_remake}, ahss::{find_ahss_issues, find_cw_issues}, automated_ahss::ahss_solver, automated_cw::cw_solver, automated_ehp::ehp_solver, constraint::solve_stem, ehp::{apply_ehp_recursively, find_ehp_issues, verify_geometric}, ehp_ahss::{ehp_to_ahss_map, set_metastable_range}, enumerate, solve::auto_deduce, tau_bockstein::{find_tau_bockstein_issues, find_tau_linearity_issues}
     }
 };
 
@@ -80,9 +98,13 @@ pub fn interactive_ahss() -> (SyntheticSS, Duration) {
                 }
                 println!("");
                 let waited_on_input = Instant::now();
-                match process_input(true) {
-                    Ok(action) => {
+                match process_input(true, &data, &STABLE_MODEL) {
+                    Ok(mut action) => {
                         total_input_time += waited_on_input.elapsed();
+                        if let Err(e) = action.resolve_names(&STABLE_MODEL) {
+                            println!("\n{e}. Please try again.");
+                            continue;
+                        }
                         if let Action::Revert { times } = action {
                             data = revert_log_and_remake(times, &mut log, &STABLE_MODEL, &original_data, true);
                             write_all(&data, &STABLE_MODEL, &log, true);
@@ -208,9 +230,13 @@ pub fn interactive_ehp() -> (SyntheticSS, Duration) {
                 }
                 println!("");
                 let waited_on_input = Instant::now();
-                match process_input(false) {
-                    Ok(action) => {
+                match process_input(false, &data, &MODEL) {
+                    Ok(mut action) => {
                         total_input_time += waited_on_input.elapsed();
+                        if let Err(e) = action.resolve_names(&MODEL) {
+                            println!("\n{e}. Please try again.");
+                            continue;
+                        }
                         if let Action::Revert { times } = action {
                             data = revert_log_and_remake(times, &mut log, &MODEL, &original_data, false);
                             write_all(&data, &MODEL, &log, false);
@@ -293,3 +319,412 @@ pub fn automated_ehp(minimal: bool) -> SyntheticSS {
 
     ehp
 }
+
+/// Replay the saved AHSS log, and the EHP log on top of it unless `ahss`.
+fn replay_logs(ahss: bool) -> SyntheticSS {
+    let mut ahss_log = get_log(false, true).unwrap_or_default();
+    let ahss_data = revert_log_and_remake(0, &mut ahss_log, &STABLE_MODEL, &STABLE_DATA, true);
+    if ahss {
+        return ahss_data;
+    }
+
+    let mut original_data = DATA.clone();
+    set_metastable_range(&mut original_data, &ahss_data).unwrap();
+
+    let mut log = get_log(false, false).unwrap_or_default();
+    let mut data = revert_log_and_remake(0, &mut log, &MODEL, &original_data, false);
+    for i in 2..=MAX_STEM {
+        let _ = apply_ehp_recursively(&mut data, &MODEL, i, false);
+    }
+    data
+}
+
+/// `ahss` or `ehp`, as the first argument of the commands that work on either.
+fn parse_sequence(arg: Option<&String>) -> Option<bool> {
+    match arg.map(String::as_str) {
+        Some("ahss") => Some(true),
+        Some("ehp") => Some(false),
+        _ => None,
+    }
+}
+
+/// `constraint ahss|ehp <stem> [top_trunc]` replays the saved logs and runs the
+/// constraint backend on a single stem, printing which candidate facts up to
+/// filtration `top_trunc` are forced and which stay ambiguous.
+pub fn constraint_command(args: &[String]) {
+    let int = |i: usize| args.get(i).and_then(|a| a.parse::<i32>().ok());
+    let (Some(ahss), Some(stem)) = (parse_sequence(args.first()), int(1)) else {
+        eprintln!("Usage: constraint ahss|ehp <stem> [top_trunc]");
+        std::process::exit(2);
+    };
+    let top_trunc = int(2).unwrap_or(MAX_STEM);
+    let start = Instant::now();
+
+    let model = if ahss { &STABLE_MODEL } else { &MODEL };
+    let data = replay_logs(ahss);
+    let report = solve_stem(&data, model, stem, top_trunc, 64, ahss);
+    report.print(model);
+
+    println!("\nProgram took: {:.2?}\n", start.elapsed());
+}
+
+/// `enumerate ahss|ehp <stem> [limit]` replays the saved logs and lists every
+/// consistent configuration of the `Unknown` facts in a single stem (the first
+/// `limit`, 4096 by default), with the homotopy orders of each.
+pub fn enumerate_command(args: &[String]) {
+    let stem = args.get(1).and_then(|a| a.parse::<i32>().ok());
+    let limit = args.get(2).map(|a| a.parse::<usize>());
+    let (Some(ahss), Some(stem), None | Some(Ok(_))) = (parse_sequence(args.first()), stem, &limit) else {
+        eprintln!("Usage: enumerate ahss|ehp <stem> [limit]");
+        std::process::exit(2);
+    };
+    let limit = limit.and_then(Result::ok).unwrap_or(1 << 12);
+    let start = Instant::now();
+
+    let model = if ahss { &STABLE_MODEL } else { &MODEL };
+    let data = replay_logs(ahss);
+    let report = enumerate::enumerate_stem(&data, model, stem, limit, ahss);
+    report.print(model);
+
+    println!("\nProgram took: {:.2?}\n", start.elapsed());
+}
+
+/// `sseq ahss|ehp` replays the saved logs and writes the sequence as sseq JSON
+/// charts to `sseq/`.
+pub fn sseq_command(args: &[String]) {
+    let Some(ahss) = parse_sequence(args.first()) else {
+        eprintln!("Usage: sseq ahss|ehp");
+        std::process::exit(2);
+    };
+    let model = if ahss { &STABLE_MODEL } else { &MODEL };
+    let data = replay_logs(ahss);
+    if let Err(e) = write_sseq_charts(&data, model, ahss) {
+        eprintln!("Could not write the sseq charts: {e}");
+        std::process::exit(1);
+    }
+}
+
+/// `json ahss|ehp` replays the saved logs and writes the full JSON export,
+/// pages included, to `export/`.
+pub fn json_command(args: &[String]) {
+    let Some(ahss) = parse_sequence(args.first()) else {
+        eprintln!("Usage: json ahss|ehp");
+        std::process::exit(2);
+    };
+    let model = if ahss { &STABLE_MODEL } else { &MODEL };
+    let data = replay_logs(ahss);
+    if let Err(e) = write_full_export(&data, model, ahss) {
+        eprintln!("Could not write the JSON export: {e}");
+        std::process::exit(1);
+    }
+}
+
+/// `tikz sphere <n> <page>` or `tikz rp <bot> <top> <page>` replays the saved
+/// logs and draws `page` (a number or `inf`) of the EHP truncation of `S^n` or
+/// the AHSS of `RP_bot^top` as TikZ to `tikz/`.
+pub fn tikz_command(args: &[String]) {
+    let int = |i: usize| args.get(i).and_then(|a| a.parse::<i32>().ok());
+    let page = |i: usize| match args.get(i).map(String::as_str) {
+        Some("inf") => Some(E_INFINITY),
+        _ => int(i),
+    };
+    let written = match (args.first().map(String::as_str), int(1), int(2)) {
+        (Some("sphere"), Some(sphere), _) if let Some(page) = page(2) => {
+            write_tikz_sphere(&replay_logs(false), &MODEL, sphere, page)
+        }
+        (Some("rp"), Some(bot_trunc), Some(top_trunc)) if let Some(page) = page(3) => {
+            write_tikz_rp(&replay_logs(true), &STABLE_MODEL, bot_trunc, top_trunc, page)
+        }
+        _ => {
+            eprintln!("Usage: tikz sphere <n> <page> | tikz rp <bot> <top> <page>");
+            std::process::exit(2);
+        }
+    };
+    if let Err(e) = written {
+        eprintln!("Could not write the TikZ chart: {e}");
+        std::process::exit(1);
+    }
+}
+
+/// `svg sphere <n>...` renders every page of the EHP truncations of the given
+/// spheres, `svg rp <bot> <top>` of an AHSS truncation.
+pub fn svg_command(args: &[String]) {
+    let numbers: Option<Vec<i32>> = args.iter().skip(1).map(|a| a.parse().ok()).collect();
+    let written = match (args.first().map(String::as_str), numbers.as_deref()) {
+        (Some("sphere"), Some(spheres)) if !spheres.is_empty() => {
+            let data = replay_logs(false);
+            spheres
+                .iter()
+                .map(|&n| write_svg_charts(&format!("ehp_S{n}"), &data, &MODEL, 0, n - 1))
+                .collect::<Result<Vec<_>, _>>()
+                .map(|paths| paths.concat())
+        }
+        (Some("rp"), Some(&[bot_trunc, top_trunc])) => {
+            let data = replay_logs(true);
+            let name = format!("ahss_RP{bot_trunc}_{top_trunc}");
+            write_svg_charts(&name, &data, &STABLE_MODEL, bot_trunc, top_trunc)
+        }
+        _ => {
+            println!("Usage: svg sphere <n>... | svg rp <bot> <top>");
+            return;
+        }
+    };
+
+    match written {
+        Ok(paths) => {
+            for path in paths {
+                println!("Wrote {}", path.display());
+            }
+        }
+        Err(e) => {
+            println!("Could not write the charts: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `table <file>` writes the homotopy groups of spheres to `file`, formatted
+/// according to its extension (`.csv`, `.md` or `.tex`). Exits with status 2
+/// on a usage error and 1 if the file cannot be written.
+pub fn table_command(args: &[String]) {
+    let path = args.first().map(PathBuf::from).unwrap_or_default();
+    let Some(format) = TableFormat::from_path(&path) else {
+        eprintln!("Usage: table <file.csv | file.md | file.tex>");
+        std::process::exit(2);
+    };
+
+    let data = replay_logs(false);
+    match export_homotopy_table(&data, &path, format) {
+        Ok(()) => println!("Wrote {}", path.display()),
+        Err(e) => {
+            eprintln!("Could not write {}: {e}", path.display());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `verify [report.json]` replays the EHP log and checks it against the
+/// classical homotopy groups, optionally writing the full report as JSON.
+/// Exits with a non-zero status if anything does not agree or the report
+/// cannot be written.
+pub fn verify_command(args: &[String]) {
+    let table = match ClassicalTable::read(&ClassicalTable::default_path()) {
+        Ok(table) => table,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(2);
+        }
+    };
+    let data = replay_logs(false);
+    let report = verify_geometric(&data, &table);
+    report.print_mismatches();
+
+    if let Some(path) = args.first().map(PathBuf::from) {
+        match serde_json::to_string_pretty(&report).map(|json| fs::write(&path, json)) {
+            Ok(Ok(())) => println!("Wrote {}", path.display()),
+            Ok(Err(e)) => {
+                eprintln!("Could not write {}: {e}", path.display());
+                std::process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("Could not serialize the report: {e}");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if report.passed() {
+        println!("Verification passed up to stem {}", report.max_stem);
+    } else {
+        std::process::exit(1);
+    }
+}
+
+/// `search sphere <n> <filters>` or `search rp <bot> <top> <filters>` lists the
+/// generators matching the filters (see [`crate::domain::query::GeneratorQuery::parse`])
+/// with their fate in that truncation.
+pub fn search_command(args: &[String]) {
+    let int = |i: usize| args.get(i).and_then(|a| a.parse::<i32>().ok());
+    match (args.first().map(String::as_str), int(1), int(2)) {
+        (Some("sphere"), Some(n), _) => {
+            let data = replay_logs(false);
+            print_search(&data, &MODEL, false, 0, n - 1, &args[2..].join(" "));
+        }
+        (Some("rp"), Some(bot_trunc), Some(top_trunc)) => {
+            let data = replay_logs(true);
+            print_search(&data, &STABLE_MODEL, true, bot_trunc, top_trunc, &args[3..].join(" "));
+        }
+        _ => {
+            eprintln!("Usage: search sphere <n> <filters> | search rp <bot> <top> <filters>");
+            std::process::exit(2);
+        }
+    }
+}
+
+/// `cw <spectrum.json> [facts.json]` builds the AHSS of a CW spectrum described
+/// in the file (see [`CwSpectrum`]), solves it from the listed facts with
+/// [`cw_solver`] and lists, per stem, where it does not converge to its Adams
+/// E2 data. The facts the solver found are written to `facts.json` if given,
+/// in the format of the `facts` of the file. Exits non-zero if the solver left
+/// a fact open or the result does not match.
+pub fn cw_command(args: &[String]) {
+    let Some(path) = args.first().map(PathBuf::from) else {
+        eprintln!("Usage: cw <spectrum.json> [facts.json]");
+        std::process::exit(2);
+    };
+    let stable = replay_logs(true);
+    let read = CwSpectrum::read(&path).and_then(|spectrum| {
+        let (model, data) = spectrum.model(&stable)?;
+        let expected = spectrum.adams_e2()?;
+        Ok((spectrum, model, data, expected))
+    });
+    let (mut spectrum, model, data, expected) = match read {
+        Ok(read) => read,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(2);
+        }
+    };
+
+    let max_stem = spectrum.max_stem().min(expected.keys().map(|&(stem, _)| stem).max().unwrap_or(0));
+    let (facts, solved) = cw_solver(&spectrum, &model, data, &expected, max_stem);
+    println!("The solver found {} facts", facts.len());
+    if let Some(out) = args.get(1) {
+        let written = serde_json::to_string_pretty(&facts)
+            .map_err(|e| e.to_string())
+            .and_then(|json| fs::write(out, json + "\n").map_err(|e| e.to_string()));
+        if let Err(e) = written {
+            eprintln!("Could not write {out}: {e}");
+            std::process::exit(1);
+        }
+    }
+
+    spectrum.facts.extend(facts);
+    let data = match spectrum.model(&stable) {
+        Ok((_, data)) => data,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    };
+    let mut passed = solved;
+    for stem in spectrum.bot_cell()..=max_stem {
+        if let Err(issues) = find_cw_issues(&data, &model, &spectrum, &expected, stem) {
+            passed = false;
+            println!("Stem {stem}:");
+            for issue in issues {
+                println!("  {issue:?}");
+            }
+        }
+    }
+
+    if passed {
+        println!("The AHSS of {} converges to its Adams E2 data up to stem {max_stem}", spectrum.name);
+    } else {
+        if !solved {
+            println!("The solver could not decide every fact up to stem {max_stem}");
+        }
+        std::process::exit(1);
+    }
+}
+
+/// `resolve sphere <max_stem> [max_s]` or `resolve rp <bot> <top> <max_stem>
+/// [max_s]` computes the comparison data by a minimal resolution (see
+/// [`resolved_compare_data`]) and prints it per stem. If there is a CSV of
+/// Lin's program, its Ext up to `max_s` is checked against the resolution,
+/// exiting non-zero if they differ.
+pub fn resolve_command(args: &[String]) {
+    let int = |i: usize| args.get(i).and_then(|a| a.parse::<i32>().ok());
+    let (bot_trunc, top_trunc, max_stem, max_s) = match (args.first().map(String::as_str), int(1), int(2), int(3)) {
+        (Some("sphere"), Some(max_stem), max_s, _) => (0, 0, max_stem, max_s),
+        (Some("rp"), Some(bot_trunc), Some(top_trunc), Some(max_stem)) => (bot_trunc, top_trunc, max_stem, int(4)),
+        _ => {
+            eprintln!("Usage: resolve sphere <max_stem> [max_s] | resolve rp <bot> <top> <max_stem> [max_s]");
+            std::process::exit(2);
+        }
+    };
+    // Above the line of slope 1/2 there are only h_0-towers.
+    let max_s = max_s.unwrap_or(max_stem / 2 + 4);
+
+    let resolved = match resolved_compare_data(bot_trunc, top_trunc, max_stem, max_s) {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(2);
+        }
+    };
+    let af_shift = if (bot_trunc, top_trunc) == (0, 0) { 0 } else { 1 };
+    for stem in bot_trunc..=max_stem {
+        let row = resolved.iter().filter(|((s, _), _)| *s == stem).sorted_by_key(|((_, af), _)| *af);
+        println!("Stem {stem}: {}", row.map(|((_, af), t)| format!("{af}: {t:?}")).join(", "));
+    }
+
+    let csv = if (bot_trunc, top_trunc) == (0, 0) {
+        Some(S0.clone())
+    } else {
+        RP.get(&(bot_trunc, top_trunc)).cloned()
+    };
+    let Some(csv) = csv else {
+        return;
+    };
+    let in_range = |&(stem, af): &(i32, i32)| stem <= max_stem && af - af_shift <= max_s;
+    let keys: Vec<_> = resolved.keys().chain(csv.keys()).copied().filter(in_range).unique().sorted().collect();
+    let mut passed = true;
+    for key in keys {
+        let (ours, theirs) = (resolved.get(&key), csv.get(&key));
+        if ours != theirs {
+            passed = false;
+            println!("Stem {}, AF {}: resolved {ours:?}, CSV {theirs:?}", key.0, key.1);
+        }
+    }
+    if passed {
+        println!("The resolution agrees with the CSV up to stem {max_stem} and s {max_s}");
+    } else {
+        std::process::exit(1);
+    }
+}
+
+/// `bockstein [max_stem] [max_s]` checks `S0` mod τ against Ext from the
+/// built-in resolution, with τ inverted against the stable stems (see
+/// [`find_tau_bockstein_issues`]) and its τ-exponents against the h_i-products
+/// of Ext (see [`find_tau_linearity_issues`]), listing the issues per stem and
+/// exiting non-zero if there are any.
+pub fn bockstein_command(args: &[String]) {
+    let int = |i: usize| args.get(i).and_then(|a| a.parse::<i32>().ok());
+    let max_stem = int(0).unwrap_or(MAX_VERIFY_STEM).min(MAX_VERIFY_STEM);
+    let max_s = int(1).unwrap_or(max_stem / 2 + 4);
+    let table = match ClassicalTable::read(&ClassicalTable::default_path()) {
+        Ok(table) => table,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(2);
+        }
+    };
+
+    let resolution = Resolution::new(ProjectiveModule::rp(0, 0), max_stem, max_s);
+    let ext = resolution.ext();
+    let mut passed = true;
+    for stem in 0..=max_stem {
+        let issues: Vec<_> = [
+            find_tau_bockstein_issues(&S0_ZEROES, &ext, &table, max_s, stem),
+            find_tau_linearity_issues(&S0_ZEROES, &resolution, &ext, max_s, stem),
+        ]
+        .into_iter()
+        .filter_map(Result::err)
+        .flatten()
+        .collect();
+        if !issues.is_empty() {
+            passed = false;
+            println!("Stem {stem}:");
+            for issue in issues {
+                println!("  {issue:?}");
+            }
+        }
+    }
+
+    if passed {
+        println!("S0 agrees with Ext mod τ, its products and the stable stems up to stem {max_stem}");
+    } else {
+        std::process::exit(1);
+    }
+}
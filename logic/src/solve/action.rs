@@ -1,6 +1,6 @@
 //! The [`Action`] log: the editable record of facts a user (or the solver) has
-//! asserted — differentials, internal/external taus, E1 generators, induced
-//! names, and reverts. [`process_action`] applies one action to the data
+//! asserted — differentials, internal/external taus, hidden 2-extensions, E1
+//! generators, induced names, and reverts. [`process_action`] applies one action to the data
 //! (expanding it across spheres via James periodicity in AHSS mode), and
 //! [`revert_log_and_remake`] replays a whole log from scratch.
 
@@ -11,8 +11,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     MAX_STEM,
-    data::naming::{generate_names_from_tag, name_to_sphere},
-    domain::{e1::E1, model::SyntheticSS, process::ehp_recursion},
+    data::naming::{generate_names_from_tag, name_get_tag, name_to_sphere},
+    domain::{e1::{E1, ResolveError}, model::SyntheticSS, process::ehp_recursion},
     types::{Kind, Torsion},
 };
 
@@ -56,6 +56,12 @@ pub enum Action {
         kind: Kind,
         proof: Option<String>,
     },
+    AddTwoExt {
+        from: String,
+        to: String,
+        sphere: i32,
+        proof: Option<String>,
+    },
     SetE1 {
         tag: String,
         torsion: Torsion,
@@ -72,6 +78,38 @@ pub enum Action {
     },
 }
 
+impl Action {
+    /// Replace every generator name by its canonical E1 name, so that the
+    /// actions typed in and the logs may use any spelling [`E1::resolve`]
+    /// accepts. `SetE1` resolves its tag as a generator on the first cell.
+    pub fn resolve_names(&mut self, model: &E1) -> Result<(), ResolveError> {
+        let resolve = |name: &mut String| -> Result<(), ResolveError> {
+            *name = model.name(model.resolve(name)?).to_string();
+            Ok(())
+        };
+        match self {
+            Action::AddDiff { from, to, .. }
+            | Action::AddInt { from, to, .. }
+            | Action::AddExt { from, to, .. }
+            | Action::AddTwoExt { from, to, .. } => {
+                resolve(from)?;
+                resolve(to)
+            }
+            Action::SetE1 { tag, .. } => {
+                if let Ok(id) = model.resolve(&format!("{tag}[1]")) {
+                    *tag = name_get_tag(model.name(id)).to_string();
+                }
+                Ok(())
+            }
+            Action::SetInducedName { name, new_name, .. } => {
+                resolve(name)?;
+                resolve(new_name)
+            }
+            Action::Revert { .. } => Ok(()),
+        }
+    }
+}
+
 pub fn process_action(data: &mut SyntheticSS, model: &E1, action: &Action, ahss: bool) -> Result<i32, ()> {
     match action {
         Action::AddDiff {
@@ -214,7 +252,7 @@ pub fn process_action(data: &mut SyntheticSS, model: &E1, action: &Action, ahss:
             // Then we apply James periodicity
             if ahss {
                 let from_id = model.get_index(from);
-                if let Some(source_torsion) = data.generators[from_id].0 {
+                if let Some(source_torsion) = data.generators()[from_id].0 {
                     if model.get_name(from).af - source_torsion == model.get_name(to).af {
                         let from_start = name_to_sphere(&from);
                         let to_start = name_to_sphere(&to);
@@ -249,6 +287,26 @@ pub fn process_action(data: &mut SyntheticSS, model: &E1, action: &Action, ahss:
             data.add_ext_tau_name(model, from.clone(), to.clone(), *af, proof.clone(), *kind)?;
             Ok(2)
         }
+        Action::AddTwoExt {
+            from,
+            to,
+            sphere,
+            proof,
+        } => {
+            if ahss {
+                panic!("We have no 2-extensions in AHSS mode")
+            }
+            let from_id = model.try_index(from).ok_or(())?;
+            let to_id = model.try_index(to).ok_or(())?;
+
+            if model.stem(from_id) != model.stem(to_id) {
+                println!("Tried to add a 2-extension between different stems");
+                return Err(());
+            }
+
+            data.add_two_extension(from_id, to_id, *sphere, proof.clone());
+            Ok(2)
+        }
         Action::SetE1 {
             tag,
             torsion,
@@ -306,7 +364,11 @@ pub fn revert_log_and_remake(
     // SetE1 actions must run before everything else (later actions depend on the
     // E1 torsion they establish), so replay the log in two passes.
     let apply = |data: &mut SyntheticSS, action: &Action| {
-        process_action(data, model, action, ahss).expect(&format!(
+        let mut action = action.clone();
+        if let Err(e) = action.resolve_names(model) {
+            panic!("There was an invalid name in the log: {e}");
+        }
+        process_action(data, model, &action, ahss).expect(&format!(
             "There was an invalid action in the log. That should not be possible :( {action:?}"
         ));
     };
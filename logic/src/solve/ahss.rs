@@ -2,13 +2,15 @@
 //! the relevant RP^n truncations for a stem and checks both synthetic and
 //! algebraic convergence against the reference data, while
 //! [`ahss_synthetic_e1_issue`] checks the E1 page itself against S0.
+//! [`find_cw_issues`] does the synthetic check for a [`CwSpectrum`] instead, as
+//! used by [`crate::solve::automated_cw`].
 
 use std::collections::HashMap;
 
 use itertools::Itertools;
 
 use crate::{
-    data::{naming::name_get_tag, r#static::{S0_ZEROES, algebraic_rp, rp_truncations, synthetic_rp}},
+    data::{cw::CwSpectrum, naming::name_get_tag, r#static::{S0_ZEROES, algebraic_rp, rp_truncations, synthetic_rp}},
     domain::{e1::E1, model::SyntheticSS, process::try_compute_pages, ss::SSPages},
     solve::{action::Action, issues::{
         Issue, compare_algebraic, compare_algebraic_spectral_sequence, compare_synthetic,
@@ -79,12 +81,28 @@ pub fn find_ahss_issues(data: &SyntheticSS, model: &E1, stem: i32) -> Result<(),
     Ok(())
 }
 
+/// Check the convergence of the AHSS of `spectrum` (`data` on the model built
+/// by [`CwSpectrum::model`]) in `stem` against its Adams E2 data `expected`.
+pub fn find_cw_issues(
+    data: &SyntheticSS,
+    model: &E1,
+    spectrum: &CwSpectrum,
+    expected: &HashMap<(i32, i32), Vec<Torsion>>,
+    stem: i32,
+) -> Result<(), Vec<Issue>> {
+    let (bot_trunc, top_trunc) = (spectrum.bot_cell(), spectrum.top_cell());
+    let pages = try_compute_pages(data, model, bot_trunc, top_trunc, stem, stem, true)?;
+    let observed = pages.convergence_at_stem(model, stem);
+
+    compare_synthetic(&observed, expected, bot_trunc, top_trunc, stem)
+}
+
 pub fn ahss_synthetic_e1_issue(data: &SyntheticSS, model: &E1, stem: i32) -> Result<(), Vec<Issue>> {
     let mut observed = HashMap::new();
     for id in model.gens_id_in_stem(stem) {
         let g = model.get(*id);
         if g.y == 1 && g.stem == stem {
-            observed.entry(g.af - 1).or_insert(vec![]).push(data.generators[*id]);
+            observed.entry(g.af - 1).or_insert(vec![]).push(data.generators()[*id]);
         }
     }
 
@@ -1,7 +1,7 @@
-//! Logic shared by the two automated solvers, [`crate::solve::automated_ahss`]
-//! and [`crate::solve::automated_ehp`].
+//! Logic shared by the automated solvers, [`crate::solve::automated_ahss`],
+//! [`crate::solve::automated_ehp`] and [`crate::solve::automated_cw`].
 //!
-//! Both solvers perform the same speculative branch-and-bound: propose a
+//! The solvers perform the same speculative branch-and-bound: propose a
 //! differential or tau-multiplication, explore the "it exists" and "it doesn't"
 //! worlds, and commit once one side is contradicted. The pieces that don't
 //! depend on which sequence we're solving live here:
@@ -236,22 +236,22 @@ pub fn filter_diff(
 
     if y - model.y(d.to) < RADON_HURWITZ_NUMBERS[y as usize] {
         Some(Kind::MinimalLength)
-    } else if data.in_diffs[d.to]
+    } else if data.in_diffs()[d.to]
         .iter()
-        .any(|from| model.y(*from) == top_trunc && data.generators[*from].alive())
+        .any(|from| model.y(*from) == top_trunc && data.generators()[*from].alive())
     {
         Some(Kind::AdditiveStructure)
     } else if bot_trunc & 1 == 0
-        && let Some(alg_to) = alg.out_diffs[d.from].first()
-        && data.generators[*alg_to].alive()
+        && let Some(alg_to) = alg.out_diffs()[d.from].first()
+        && data.generators()[*alg_to].alive()
         && model.y(*alg_to) + 1 == bot_trunc
     {
         Some(Kind::Invisible)
     } else if top_trunc & 1 == 1
         && !(ahss && top_trunc == 5 && bot_trunc == 3)
         && let Some(dies) = model.get(d.to).dies
-        && let Some(source) = alg.in_diffs[d.to].first()
-        && data.generators[*source].free()
+        && let Some(source) = alg.in_diffs()[d.to].first()
+        && data.generators()[*source].free()
         && top_trunc + 2 == dies
     {
         Some(Kind::Unnecessary)
@@ -116,7 +116,7 @@ fn iterate_e1_issues(
 
                 // Apply possible solution to torsion on E_1 page
                 for j in &x.0 {
-                    data.generators[j.0] = j.1;
+                    data.set_torsion(j.0, j.1);
                 }
 
                 let res = ahss_iterate(data, model, alg_ahss, alg_data, e1_issues, g.clone(), log.clone(), stem, top_trunc, bot_trunc, depth + 1);
@@ -134,7 +134,7 @@ fn iterate_e1_issues(
 
                 // Apply possible solution to torsion on E_1 page
                 for j in &x.0 {
-                    data.generators[j.0] = j.1;
+                    data.set_torsion(j.0, j.1);
                 }
 
                 let res = ahss_iterate(data, model, alg_ahss, alg_data, e1_issues, getout.clone(), log.clone(), stem, top_trunc, bot_trunc, depth + 1);
@@ -196,7 +196,7 @@ fn iterate_e1_issues(
                         if (depth == 0 || ALWAYS_PRINT) && model.get(j.0).y == 1 {
                             println!("Set E1 torsion {index}: {} | {:?}", model.get(j.0).name, j.1);
                         }
-                        data.generators[j.0] = j.1;
+                        data.set_torsion(j.0, j.1);
                     }
                     return None;
                 }
@@ -705,7 +705,7 @@ pub fn ahss_solver(log: Option<Vec<Action>>) -> (Vec<Action>, SyntheticSS) {
         (MAX_STEM + 1) as usize
     ];
 
-    for (&(from, to), (kind, _)) in &alg_ahss.from_to {
+    for (&(from, to), (kind, _)) in alg_ahss.facts() {
         let d_y = model.y(from) - model.y(to);
         let repeats = D_R_REPEATS[d_y as usize];
         if d_y == 1 || model.y(to) - (repeats as i32) >= 1 {
@@ -737,7 +737,7 @@ pub fn ahss_solver(log: Option<Vec<Action>>) -> (Vec<Action>, SyntheticSS) {
 
     // Rebuild the final sequence from the produced log on top of the algebraic
     // differentials, so the result is exactly what replaying the log yields.
-    for (&(from, to), (kind, _)) in &alg_ahss.from_to {
+    for (&(from, to), (kind, _)) in alg_ahss.facts() {
         partial_ahss.add_diff(model, from, to, None, *kind);
     }
 
@@ -0,0 +1,281 @@
+//! The automated solver for the AHSS of a [`CwSpectrum`]. [`cw_solver`] runs
+//! the same speculative search as [`crate::solve::automated_ahss`] on the
+//! model [`CwSpectrum::model`] builds: per stem it walks the pairs of cells
+//! from the shortest differential to the longest, proposes differentials
+//! between them with [`get_a_diff`] and commits each as Real or Fake by
+//! exploring both worlds.
+//!
+//! Only the whole spectrum has comparison data, so a stem is checked once all
+//! its pairs of cells are done ([`find_cw_issues`]), and tau-multiplications
+//! are proposed from the issues found there. There is no James periodicity
+//! and no vector field bound, so facts are added one at a time and
+//! [`crate::solve::automated::filter_diff`] is not used.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    data::cw::CwSpectrum,
+    domain::{
+        e1::E1,
+        model::{Diff, ExtTauMult, SyntheticSS},
+    },
+    solve::{
+        action::Action,
+        ahss::find_cw_issues,
+        automated::{
+            ALWAYS_PRINT, Commitment, MAX_DEPTH, TauIssue, classify_tau_issue, commit_diff_choice,
+            commit_tau_choice,
+        },
+        generate::get_a_diff,
+        issues::Issue,
+        search::{
+            BranchResult, ChoiceResult, GetOut, SpeculativeBranchOutcome, branch_on_speculative_worlds,
+            check_getout, create_getout, empty_getout, signal_parent_getout,
+        },
+        solve::{suggest_tau_solution_algebraic, suggest_tau_solution_generator_synthetic},
+    },
+    types::{Kind, Torsion},
+};
+
+/// What the search needs besides the data: the model, the spectrum and its
+/// Adams E2 data, and the last stem to solve.
+struct Cw<'a> {
+    model: &'a E1,
+    spectrum: &'a CwSpectrum,
+    expected: &'a HashMap<(i32, i32), Vec<Torsion>>,
+    max_stem: i32,
+}
+
+/// A pair of cells, by index into the cells: differentials go from `top` to
+/// `bot`. With a single cell this is `(0, 0)` and there are no differentials.
+#[derive(Debug, Clone, Copy)]
+struct Position {
+    stem: i32,
+    top: usize,
+    bot: usize,
+}
+
+impl Position {
+    fn first(cw: &Cw, stem: i32) -> Self {
+        let top = (cw.spectrum.cells.len() > 1) as usize;
+        Position { stem, top, bot: top.saturating_sub(1) }
+    }
+
+    /// The last pair of a stem is the whole spectrum, where it is checked.
+    fn is_last(&self, cw: &Cw) -> bool {
+        self.top + 1 == cw.spectrum.cells.len().max(1) && self.bot == 0
+    }
+
+    /// Longer differentials from the same top cell first, then the next top
+    /// cell, then the next stem.
+    fn next(self, cw: &Cw) -> Self {
+        if self.bot > 0 {
+            Position { bot: self.bot - 1, ..self }
+        } else if !self.is_last(cw) {
+            Position { top: self.top + 1, bot: self.top, ..self }
+        } else {
+            Position::first(cw, self.stem + 1)
+        }
+    }
+
+    fn cells(&self, cw: &Cw) -> (i32, i32) {
+        (cw.spectrum.cells[self.top], cw.spectrum.cells[self.bot])
+    }
+}
+
+fn is_tau_issue(data: &SyntheticSS, cw: &Cw, stem: i32) -> Result<Option<(TauIssue, Vec<Issue>)>, String> {
+    match find_cw_issues(data, cw.model, cw.spectrum, cw.expected, stem) {
+        Ok(_) => Ok(None),
+        Err(issues) => {
+            let context = format!("For {} in stem {stem}", cw.spectrum.name);
+            classify_tau_issue(issues, &context, &context)
+        }
+    }
+}
+
+fn cw_iterate(
+    mut data: SyntheticSS,
+    cw: &Cw,
+    mut getout: GetOut,
+    log: Arc<Mutex<Vec<Action>>>,
+    mut at: Position,
+    depth: i32,
+) -> BranchResult {
+    loop {
+        if at.stem > cw.max_stem || depth > MAX_DEPTH {
+            return BranchResult::Open;
+        }
+        if check_getout(&getout) {
+            return BranchResult::Cancelled;
+        }
+        let (top_cell, bot_cell) = at.cells(cw);
+
+        // 1. Propose the next plausible differential between the two cells.
+        if at.top > at.bot
+            && let Some(d) = get_a_diff(&data, cw.model, top_cell, bot_cell, at.stem)
+        {
+            if depth >= MAX_DEPTH {
+                return BranchResult::Open;
+            }
+            match try_diff(&mut data, cw, &getout, &log, at, depth, d) {
+                ChoiceResult::Chosen => continue,
+                ChoiceResult::Open => return BranchResult::Open,
+                ChoiceResult::Cancelled => return BranchResult::Cancelled,
+            }
+        }
+
+        // 2. Once every pair of cells is done, check the stem against the Adams
+        //    E2 data, looking for a tau-multiplication if that is what is off.
+        if at.is_last(cw) {
+            let potential_tau_thing = match is_tau_issue(&data, cw, at.stem) {
+                Ok(tau_issue) => tau_issue,
+                Err(is) => {
+                    signal_parent_getout(&mut getout, depth);
+                    return BranchResult::Contradiction(is);
+                }
+            };
+
+            if let Some((synthetic, mut issues)) = potential_tau_thing {
+                let (top, bot) = (cw.spectrum.top_cell(), cw.spectrum.bot_cell());
+                let option = match synthetic {
+                    TauIssue::AlgTauIssue => {
+                        suggest_tau_solution_algebraic(&data, cw.model, &mut issues, top, bot, at.stem)
+                    }
+                    TauIssue::SynTauGeneratorIssue | TauIssue::SynTauModuleIssue => {
+                        suggest_tau_solution_generator_synthetic(&data, cw.model, &mut issues, top, bot, at.stem)
+                    }
+                };
+
+                let Some(d) = option else {
+                    signal_parent_getout(&mut getout, depth);
+                    return BranchResult::Contradiction(format!(
+                        "Issue for {} in stem {}: {issues:?}",
+                        cw.spectrum.name, at.stem
+                    ));
+                };
+                if depth >= MAX_DEPTH {
+                    return BranchResult::Open;
+                }
+                match try_tau(&mut data, cw, &getout, &log, at, depth, d) {
+                    ChoiceResult::Chosen => continue,
+                    ChoiceResult::Open => return BranchResult::Open,
+                    ChoiceResult::Cancelled => return BranchResult::Cancelled,
+                }
+            }
+        }
+
+        // 3. Nothing left to decide here, move on.
+        at = at.next(cw);
+        if depth == 0 && at.top <= 1 && at.bot == 0 {
+            println!("Current stem: {}", at.stem);
+        }
+    }
+}
+
+fn try_diff(
+    data: &mut SyntheticSS,
+    cw: &Cw,
+    getout: &GetOut,
+    log: &Arc<Mutex<Vec<Action>>>,
+    at: Position,
+    depth: i32,
+    d: Diff,
+) -> ChoiceResult {
+    if ALWAYS_PRINT || depth == 0 {
+        let (from_name, to_name) = cw.model.get_names(d.from, d.to);
+        println!("Trying diff: {} | {}", from_name, to_name);
+    }
+
+    let g = create_getout(getout, 2, depth);
+    let world = |kind| {
+        let mut data = data.clone();
+        data.add_diff(cw.model, d.from, d.to, Some("".to_string()), kind);
+        cw_iterate(data, cw, g.clone(), log.clone(), at, depth + 1)
+    };
+
+    match branch_on_speculative_worlds(depth, || world(Kind::Real), || world(Kind::Fake)) {
+        SpeculativeBranchOutcome::ChooseRight(e) => {
+            commit_diff_choice(data, cw.model, log, depth, d, Commitment::Fake(e), false);
+            ChoiceResult::Chosen
+        }
+        SpeculativeBranchOutcome::ChooseLeft(e) => {
+            commit_diff_choice(data, cw.model, log, depth, d, Commitment::Real(e), false);
+            ChoiceResult::Chosen
+        }
+        SpeculativeBranchOutcome::BothOpen => {
+            commit_diff_choice(data, cw.model, log, depth, d, Commitment::Unknown, false);
+            ChoiceResult::Open
+        }
+        SpeculativeBranchOutcome::Cancelled => ChoiceResult::Cancelled,
+    }
+}
+
+fn try_tau(
+    data: &mut SyntheticSS,
+    cw: &Cw,
+    getout: &GetOut,
+    log: &Arc<Mutex<Vec<Action>>>,
+    at: Position,
+    depth: i32,
+    d: ExtTauMult,
+) -> ChoiceResult {
+    if ALWAYS_PRINT || depth == 0 {
+        let (from_name, to_name) = cw.model.get_names(d.from, d.to);
+        println!("Trying tau: {} | {} | af: {}", from_name, to_name, d.af);
+    }
+
+    let g = create_getout(getout, 2, depth);
+    let world = |kind| {
+        let mut data = data.clone();
+        data.add_ext_tau(cw.model, d.from, d.to, d.af, None, kind);
+        cw_iterate(data, cw, g.clone(), log.clone(), at, depth + 1)
+    };
+
+    match branch_on_speculative_worlds(depth, || world(Kind::Real), || world(Kind::Fake)) {
+        SpeculativeBranchOutcome::ChooseRight(e) => {
+            commit_tau_choice(data, cw.model, log, depth, d, Commitment::Fake(e), false);
+            ChoiceResult::Chosen
+        }
+        SpeculativeBranchOutcome::ChooseLeft(e) => {
+            commit_tau_choice(data, cw.model, log, depth, d, Commitment::Real(e), false);
+            ChoiceResult::Chosen
+        }
+        SpeculativeBranchOutcome::BothOpen => {
+            commit_tau_choice(data, cw.model, log, depth, d, Commitment::Unknown, false);
+            ChoiceResult::Open
+        }
+        SpeculativeBranchOutcome::Cancelled => ChoiceResult::Cancelled,
+    }
+}
+
+/// Entry point: solve the AHSS of `spectrum` up to `max_stem`, starting from
+/// `data`, the model with its algebraic differentials and listed facts
+/// ([`CwSpectrum::model`]). Returns the facts the search committed, in the
+/// format of [`CwSpectrum::facts`], and whether it got through every stem
+/// without leaving a fact open.
+pub fn cw_solver(
+    spectrum: &CwSpectrum,
+    model: &E1,
+    data: SyntheticSS,
+    expected: &HashMap<(i32, i32), Vec<Torsion>>,
+    max_stem: i32,
+) -> (Vec<Action>, bool) {
+    let cw = Cw { model, spectrum, expected, max_stem };
+    let log = Arc::new(Mutex::new(vec![]));
+    let res = cw_iterate(data, &cw, empty_getout(), log.clone(), Position::first(&cw, spectrum.bot_cell()), 0);
+    println!("{res:?}");
+
+    let log = Arc::try_unwrap(log).unwrap().into_inner().unwrap();
+    let solved = res == BranchResult::Open && !log.iter().any(is_unknown);
+    (log, solved)
+}
+
+fn is_unknown(action: &Action) -> bool {
+    matches!(
+        action,
+        Action::AddDiff { kind: Kind::Unknown, .. } | Action::AddExt { kind: Kind::Unknown, .. }
+    )
+}
@@ -127,7 +127,7 @@ fn filter_tau(
     let _stem = model.stem(d.to);
     let _y = model.y(d.from);
 
-    if data.generators[d.from].free() {
+    if data.generators()[d.from].free() {
         Some(Kind::Unnecessary)
     } else {
         None
@@ -879,7 +879,7 @@ pub fn ehp_solver(ahss: &SyntheticSS, log: Option<Vec<Action>>) -> (Vec<Action>,
 
     // Seed the EHP's own algebraic differentials (skipping the metastable ones,
     // already added above).
-    for (&(from, to), (kind, _)) in &DATA.from_to {
+    for (&(from, to), (kind, _)) in DATA.facts() {
         let d_y = MODEL.y(from) - MODEL.y(to);
         // Exclude metastable ones, as they have already been added
         if !in_metastable_range(MODEL.y(to), MODEL.stem(to)) {
@@ -901,7 +901,7 @@ pub fn ehp_solver(ahss: &SyntheticSS, log: Option<Vec<Action>>) -> (Vec<Action>,
     // Lift the AHSS's proven differentials into the EHP (the stable sequence's
     // facts must hold unstably too), skipping algebraic/unknown ones. Real
     // length-1 diffs and fakes go to the log; longer ones are deferred like above.
-    for (&(from, to), (kind, _)) in &ahss.from_to {
+    for (&(from, to), (kind, _)) in ahss.facts() {
         let d_y = STABLE_MODEL.y(from) - STABLE_MODEL.y(to);
 
         // Only add differentials here
@@ -953,28 +953,22 @@ pub fn ehp_solver(ahss: &SyntheticSS, log: Option<Vec<Action>>) -> (Vec<Action>,
 
     // Add all external tau's
     // We won't worry about the fake ones
-    for esss in &ahss.external_tau_page {
-        for ess in esss {
-            for es in ess {
-                for e in es {
-                    // Only lift taus whose endpoints both exist in the EHP model.
-                    if MODEL.try_index(STABLE_MODEL.name(e.from)).is_some()
-                        && MODEL.try_index(STABLE_MODEL.name(e.to)).is_some()
-                    {
-                        let (kind, _) = ahss
-                            .from_to
-                            .get(&(e.from, e.to))
-                            .unwrap().clone();
-                        log.push(Action::AddExt {
-                            from: STABLE_MODEL.name(e.from).to_string(),
-                            to: STABLE_MODEL.name(e.to).to_string(),
-                            af: e.af,
-                            kind: kind,
-                            proof: Some("Lifted".to_string()),
-                        });
-                    }
-                }
-            }
+    for e in ahss.ext_taus() {
+        // Only lift taus whose endpoints both exist in the EHP model.
+        if MODEL.try_index(STABLE_MODEL.name(e.from)).is_some()
+            && MODEL.try_index(STABLE_MODEL.name(e.to)).is_some()
+        {
+            let (kind, _) = ahss
+                .facts()
+                .get(&(e.from, e.to))
+                .unwrap().clone();
+            log.push(Action::AddExt {
+                from: STABLE_MODEL.name(e.from).to_string(),
+                to: STABLE_MODEL.name(e.to).to_string(),
+                af: e.af,
+                kind: kind,
+                proof: Some("Lifted".to_string()),
+            });
         }
     }
 
@@ -984,7 +978,7 @@ pub fn ehp_solver(ahss: &SyntheticSS, log: Option<Vec<Action>>) -> (Vec<Action>,
 
     // Precompute each AHSS sphere's pages once, so `check_issue` can cheaply
     // verify EHP -> AHSS compatibility while the search runs.
-    let ahss_pages = std::array::from_fn(|x| compute_pages(&ahss, &STABLE_MODEL, 0, x as i32, 0, 150, false).0);
+    let ahss_pages = std::array::from_fn(|x| Arc::unwrap_or_clone(compute_pages(&ahss, &STABLE_MODEL, 0, x as i32, 0, 150, false).0));
 
     // Drive the search from the first cell; it appends every fact to `log`.
     let res = ehp_iterate(
@@ -1003,7 +997,7 @@ pub fn ehp_solver(ahss: &SyntheticSS, log: Option<Vec<Action>>) -> (Vec<Action>,
 
     // Rebuild the final sequence from the produced log on top of the algebraic
     // differentials, so the result is exactly what replaying the log yields.
-    for (&(from, to), _) in &DATA.from_to {
+    for (&(from, to), _) in DATA.facts() {
         partial_ehp.add_diff(&MODEL, from, to, None, Kind::Algebraic);
     }
 
@@ -0,0 +1,674 @@
+//! A constraint-solver backend for the facts the speculative search leaves
+//! ambiguous. Instead of exploring one binary choice at a time (see
+//! [`crate::solve::search`]), a stem's candidate differentials and external taus
+//! become boolean variables and every reference check becomes a constraint, so
+//! we can enumerate *all* consistent assignments and read off which facts are
+//! forced and which stay ambiguous.
+//!
+//! The solver itself ([`ConstraintProblem`]) is a small DPLL-style enumerator
+//! over two kinds of constraints:
+//!
+//! - [`Cardinality`]: "between `min` and `max` of these variables are true",
+//!   used with unit propagation. These encode the additive-structure rules the
+//!   solvers already rely on (at most one differential into a target per source
+//!   row, at most one external tau out of a source per target row).
+//! - [`Theory`]: a convergence constraint against `synthetic_rp`/`algebraic_rp`
+//!   (AHSS) or `S0`/`algebraic_spheres` (EHP) for one truncation. The torsion
+//!   arithmetic of [`crate::domain::process`] is not linear, so these are
+//!   evaluated by computing the pages as soon as every variable the truncation
+//!   can see has been assigned. Truncations are ordered by their top cell, so
+//!   small truncations prune the search early.
+
+use std::{cell::RefCell, collections::HashMap};
+
+use crate::{
+    data::r#static::{RADON_HURWITZ_NUMBERS, S0, algebraic_rp, algebraic_spheres, rp_truncations, synthetic_rp},
+    domain::{
+        e1::E1,
+        model::{Diff, ExtTauMult, FromTo, SyntheticSS},
+        process::compute_pages,
+    },
+    solve::issues::{
+        Issue, compare_algebraic, compare_algebraic_spectral_sequence, compare_synthetic,
+    },
+    types::Kind,
+};
+
+/// Budget of page computations per search in [`solve_stem`].
+const MAX_CHECKS: usize = 2000;
+
+/// Between `min` and `max` (inclusive) of `vars` must be true.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cardinality {
+    pub vars: Vec<usize>,
+    pub min: usize,
+    pub max: usize,
+}
+
+pub type TheoryResult = Result<(), String>;
+pub type TheoryCheck<'a> = Box<dyn Fn(&[Option<bool>]) -> TheoryResult + Sync + 'a>;
+
+/// A constraint decided by an oracle once all of `vars` are assigned. The check
+/// receives an assignment in which only `vars` are set (everything else is
+/// `None`), so its result is a function of `vars` alone: this is what makes
+/// memoizing it on their values and blaming only them in conflicts sound.
+/// Returns a reason on failure.
+pub struct Theory<'a> {
+    pub name: String,
+    pub vars: Vec<usize>,
+    pub check: TheoryCheck<'a>,
+}
+
+pub struct ConstraintProblem<'a> {
+    pub num_vars: usize,
+    pub cardinalities: Vec<Cardinality>,
+    pub theories: Vec<Theory<'a>>,
+    /// How many (uncached) theory checks a single search may run before
+    /// giving up. Theory checks compute pages, so this bounds the runtime.
+    pub max_checks: usize,
+    /// Theory results keyed by the values of the theory's own variables, which
+    /// are all the check gets to see. The same partial assignments come up over
+    /// and over while backtracking.
+    memo: RefCell<HashMap<(usize, Vec<bool>), TheoryResult>>,
+}
+
+/// All models found, and whether the enumeration finished before hitting the
+/// model limit or the check budget (if not, `models` is a prefix of the full
+/// list).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Enumeration {
+    pub models: Vec<Vec<bool>>,
+    pub complete: bool,
+    /// Why the last branch was refuted; only interesting when `models` is empty.
+    pub refutation: Option<String>,
+    checks: usize,
+}
+
+/// Variables split by [`ConstraintProblem::backbone`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Backbone {
+    /// Same value in every model.
+    pub forced: Vec<(usize, bool)>,
+    /// Differs between two models we found.
+    pub ambiguous: Vec<usize>,
+    /// Could not be decided within the check budget.
+    pub undecided: Vec<usize>,
+}
+
+impl<'a> ConstraintProblem<'a> {
+    pub fn new(num_vars: usize) -> Self {
+        Self {
+            num_vars,
+            cardinalities: vec![],
+            theories: vec![],
+            max_checks: usize::MAX,
+            memo: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Enumerate satisfying assignments, stopping after `limit` models.
+    /// Variables are branched on in index order (false first), so callers
+    /// should number them such that theories complete as early as possible.
+    pub fn enumerate(&self, limit: usize) -> Enumeration {
+        self.enumerate_with(&[], limit)
+    }
+
+    /// As [`Self::enumerate`], with some variables fixed up front.
+    pub fn enumerate_with(&self, assumptions: &[(usize, bool)], limit: usize) -> Enumeration {
+        let mut enumeration = Enumeration {
+            models: vec![],
+            complete: true,
+            refutation: None,
+            checks: 0,
+        };
+        let mut state = State {
+            assignment: vec![None; self.num_vars],
+            reasons: vec![None; self.num_vars],
+            checked: vec![false; self.theories.len()],
+        };
+        for &(var, value) in assumptions {
+            state.assignment[var] = Some(value);
+        }
+        self.search(state, limit, &mut enumeration);
+        enumeration
+    }
+
+    /// Split the variables into those with the same value in every model
+    /// (the backbone) and those that differ between models. Rather than
+    /// enumerating everything, this finds one model and then asks for a model
+    /// with each remaining variable flipped. Returns `None` if there is no
+    /// model (or none was found within the budget).
+    pub fn backbone(&self) -> Option<Backbone> {
+        let first = self.enumerate(1).models.pop()?;
+        let mut varies = vec![false; self.num_vars];
+        let mut backbone = Backbone::default();
+
+        for var in 0..self.num_vars {
+            if varies[var] {
+                continue;
+            }
+            let flipped = self.enumerate_with(&[(var, !first[var])], 1);
+            match flipped.models.first() {
+                Some(other) => {
+                    for (v, value) in other.iter().enumerate() {
+                        if *value != first[v] {
+                            varies[v] = true;
+                        }
+                    }
+                }
+                None if flipped.complete => backbone.forced.push((var, first[var])),
+                None => backbone.undecided.push(var),
+            }
+        }
+
+        backbone.ambiguous = (0..self.num_vars).filter(|v| varies[*v]).collect();
+        Some(backbone)
+    }
+
+    fn check(&self, id: usize, assignment: &[Option<bool>], enumeration: &mut Enumeration) -> Result<(), String> {
+        let theory = &self.theories[id];
+        let key = (id, theory.vars.iter().map(|v| assignment[*v].unwrap()).collect());
+        if let Some(result) = self.memo.borrow().get(&key) {
+            return result.clone();
+        }
+
+        // Hide everything outside the theory, so the check cannot depend on
+        // variables the key and the conflict sets do not mention.
+        let mut visible = vec![None; self.num_vars];
+        for &v in &theory.vars {
+            visible[v] = assignment[v];
+        }
+
+        enumeration.checks += 1;
+        let result = (theory.check)(&visible).map_err(|e| format!("{}: {e}", theory.name));
+        self.memo.borrow_mut().insert(key, result.clone());
+        result
+    }
+
+    /// The decided variables responsible for a conflict between `vars`:
+    /// propagated variables are replaced by the variables that forced them.
+    fn conflict_set(&self, state: &State, vars: &[usize]) -> Vec<bool> {
+        let mut set = vec![false; self.num_vars];
+        let mut stack: Vec<usize> = vars.iter().copied().filter(|v| state.assignment[*v].is_some()).collect();
+        let mut seen = vec![false; self.num_vars];
+        while let Some(v) = stack.pop() {
+            if std::mem::replace(&mut seen[v], true) {
+                continue;
+            }
+            match &state.reasons[v] {
+                Some(reason) => stack.extend(reason),
+                None => set[v] = true,
+            }
+        }
+        set
+    }
+
+    /// Depth-first search with conflict-directed backjumping. Returns the
+    /// decided variables a refutation of this subtree depends on, or `None` if
+    /// the subtree contained models (or ran out of budget). When both values
+    /// of a variable are refuted by conflicts not involving some ancestor's
+    /// decision, that ancestor's other value is skipped.
+    fn search(&self, mut state: State, limit: usize, enumeration: &mut Enumeration) -> Option<Vec<bool>> {
+        if enumeration.models.len() >= limit || enumeration.checks >= self.max_checks {
+            enumeration.complete = false;
+            return None;
+        }
+
+        if let Err((e, vars)) = self.propagate(&mut state) {
+            enumeration.refutation = Some(e);
+            return Some(self.conflict_set(&state, &vars));
+        }
+
+        // Run every theory that has just become fully assigned.
+        for id in 0..self.theories.len() {
+            if !state.checked[id] && self.theories[id].vars.iter().all(|v| state.assignment[*v].is_some()) {
+                if let Err(e) = self.check(id, &state.assignment, enumeration) {
+                    enumeration.refutation = Some(e);
+                    return Some(self.conflict_set(&state, &self.theories[id].vars));
+                }
+                state.checked[id] = true;
+            }
+        }
+
+        let Some(var) = state.assignment.iter().position(|a| a.is_none()) else {
+            enumeration
+                .models
+                .push(state.assignment.into_iter().map(|a| a.unwrap()).collect());
+            return None;
+        };
+
+        // `None` once some branch had models: then there is nothing to jump over.
+        let mut conflict = Some(vec![false; self.num_vars]);
+        for value in [false, true] {
+            let mut next = state.clone();
+            next.assignment[var] = Some(value);
+            match self.search(next, limit, enumeration) {
+                // This variable played no part, so the other value fails too.
+                Some(cs) if !cs[var] && conflict.is_some() => return Some(cs),
+                Some(cs) => {
+                    if let Some(conflict) = &mut conflict {
+                        conflict.iter_mut().zip(cs).for_each(|(c, x)| *c |= x);
+                    }
+                }
+                None => conflict = None,
+            }
+        }
+        conflict.map(|mut conflict| {
+            conflict[var] = false;
+            conflict
+        })
+    }
+
+    /// Unit propagation over the cardinality constraints, to a fixpoint. On a
+    /// violation, returns the variables of the violated constraint.
+    fn propagate(&self, state: &mut State) -> Result<(), (String, Vec<usize>)> {
+        let assignment = &mut state.assignment;
+        loop {
+            let mut changed = false;
+            for c in &self.cardinalities {
+                let trues = c.vars.iter().filter(|v| assignment[**v] == Some(true)).count();
+                let open = c.vars.iter().filter(|v| assignment[**v].is_none()).count();
+
+                if trues > c.max || trues + open < c.min {
+                    return Err((
+                        format!("Cardinality {}..={} violated on {:?}", c.min, c.max, c.vars),
+                        c.vars.clone(),
+                    ));
+                }
+                if open == 0 {
+                    continue;
+                }
+
+                // All remaining open variables are forced one way.
+                let forced = if trues == c.max {
+                    Some(false)
+                } else if trues + open == c.min {
+                    Some(true)
+                } else {
+                    None
+                };
+
+                if let Some(value) = forced {
+                    let reason: Vec<usize> = c.vars.iter().copied().filter(|v| assignment[*v].is_some()).collect();
+                    for v in &c.vars {
+                        if assignment[*v].is_none() {
+                            assignment[*v] = Some(value);
+                            state.reasons[*v] = Some(reason.clone());
+                        }
+                    }
+                    changed = true;
+                }
+            }
+            if !changed {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// A node of the search: the (partial) assignment, for each propagated
+/// variable the variables that forced it, and which theories already passed.
+#[derive(Clone)]
+struct State {
+    assignment: Vec<Option<bool>>,
+    reasons: Vec<Option<Vec<usize>>>,
+    checked: Vec<bool>,
+}
+
+/// A fact whose existence we don't know yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Candidate {
+    Diff(Diff),
+    Tau(ExtTauMult),
+}
+
+impl Candidate {
+    pub fn edge(&self) -> FromTo {
+        match self {
+            Candidate::Diff(d) => (d.from, d.to),
+            Candidate::Tau(t) => (t.from, t.to),
+        }
+    }
+
+    /// The highest filtration this fact touches, i.e. the smallest top
+    /// truncation in which it is visible.
+    fn top_y(&self, model: &E1) -> i32 {
+        model.y(self.edge().0)
+    }
+
+    fn bot_y(&self, model: &E1) -> i32 {
+        model.y(self.edge().1)
+    }
+
+    pub fn describe(&self, model: &E1) -> String {
+        let (from, to) = self.edge();
+        let (from_name, to_name) = model.get_names(from, to);
+        match self {
+            Candidate::Diff(_) => format!("Differential {from_name} -> {to_name}"),
+            Candidate::Tau(t) => format!("External tau {from_name} -> {to_name} | af: {}", t.af),
+        }
+    }
+}
+
+/// The result of solving one stem: every candidate, the consistent
+/// assignments (up to the limit), and the candidates whose value is the same in
+/// all of them. The backbone is exact even if the enumeration is not, except
+/// for the facts it reports as undecided.
+#[derive(Debug, Clone)]
+pub struct ConstraintReport {
+    pub stem: i32,
+    pub candidates: Vec<Candidate>,
+    pub enumeration: Enumeration,
+    pub backbone: Backbone,
+}
+
+impl ConstraintReport {
+    pub fn print(&self, model: &E1) {
+        println!(
+            "Stem {}: {} candidates, {} consistent assignments{}",
+            self.stem,
+            self.candidates.len(),
+            self.enumeration.models.len(),
+            if self.enumeration.complete { "" } else { " (limit reached)" }
+        );
+        if self.enumeration.models.is_empty() {
+            println!(
+                "No consistent assignment: {}",
+                self.enumeration.refutation.as_deref().unwrap_or("unknown")
+            );
+            return;
+        }
+        // Most candidates are forced Fake, so only list the Real ones.
+        let mut fake = 0;
+        for &(id, value) in &self.backbone.forced {
+            if value {
+                println!("Forced Real: {}", self.candidates[id].describe(model));
+            } else {
+                fake += 1;
+            }
+        }
+        println!("Forced Fake: {fake} candidates");
+        for &id in &self.backbone.ambiguous {
+            println!("Ambiguous: {}", self.candidates[id].describe(model));
+        }
+        for &id in &self.backbone.undecided {
+            println!("Undecided: {}", self.candidates[id].describe(model));
+        }
+    }
+}
+
+/// Apply the assigned candidates to a copy of `base`: true becomes a Real fact,
+/// false a Fake one, unassigned ones are left out.
+pub fn apply_assignment(
+    base: &SyntheticSS,
+    model: &E1,
+    candidates: &[Candidate],
+    assignment: &[Option<bool>],
+) -> SyntheticSS {
+    let mut data = base.clone();
+    for (c, a) in candidates.iter().zip(assignment) {
+        let Some(value) = a else { continue };
+        let kind = if *value { Kind::Real } else { Kind::Fake };
+        match c {
+            Candidate::Diff(d) => data.add_diff(model, d.from, d.to, None, kind),
+            Candidate::Tau(t) => data.add_ext_tau(model, t.from, t.to, t.af, None, kind),
+        }
+    }
+    data
+}
+
+/// The candidate differentials into `stem` and external taus within `stem`
+/// whose source lies at most at filtration `top_trunc`. Facts already decided
+/// (anything but `Unknown`) are left alone; `Unknown` ones are reopened.
+pub fn stem_candidates(data: &SyntheticSS, model: &E1, stem: i32, top_trunc: i32) -> Vec<Candidate> {
+    let open = |from: usize, to: usize| match data.facts().get(&(from, to)) {
+        Some((kind, _)) => *kind == Kind::Unknown,
+        None => true,
+    };
+    let mut candidates = vec![];
+
+    for &t_id in model.gens_id_in_stem(stem) {
+        if !data.generators()[t_id].alive() {
+            continue;
+        }
+        let t_y = model.y(t_id);
+
+        for &s_id in model.gens_id_in_stem(stem + 1) {
+            let s_y = model.y(s_id);
+            if s_y <= t_y || s_y > top_trunc || !data.generators()[s_id].alive() {
+                continue;
+            }
+            // Same shortcuts `filter_diff` takes: too short to exist, or a
+            // non-positive coefficient already at E1.
+            if s_y - t_y < RADON_HURWITZ_NUMBERS[s_y as usize] || model.af(t_id) <= model.af(s_id) {
+                continue;
+            }
+            if open(s_id, t_id) {
+                candidates.push(Candidate::Diff(Diff { from: s_id, to: t_id }));
+            }
+        }
+
+        for &s_id in model.gens_id_in_stem(stem) {
+            let s_y = model.y(s_id);
+            if s_y <= t_y || s_y > top_trunc {
+                continue;
+            }
+            // Only a torsion source can carry an external tau.
+            if let Some(s_torsion) = data.generators()[s_id].0
+                && s_torsion > 0
+                && model.af(s_id) > model.af(t_id)
+                && model.af(s_id) - s_torsion <= model.af(t_id)
+                && open(s_id, t_id)
+            {
+                candidates.push(Candidate::Tau(ExtTauMult {
+                    from: s_id,
+                    to: t_id,
+                    af: model.af(t_id) + 1,
+                }));
+            }
+        }
+    }
+
+    // Branch on facts in the order small truncations see them.
+    candidates.sort_by_key(|c| (c.top_y(model), -c.bot_y(model)));
+    candidates
+}
+
+/// The additive-structure rules as cardinality constraints: at most one Real
+/// differential into a target from each source row, and at most one Real
+/// external tau out of a source into each target row. Facts already Real in
+/// `data` between live generators use up that allowance.
+fn additive_constraints(data: &SyntheticSS, model: &E1, candidates: &[Candidate]) -> Vec<Cardinality> {
+    let mut groups: Vec<((bool, usize, i32), Vec<usize>)> = vec![];
+    for (id, c) in candidates.iter().enumerate() {
+        let key = match c {
+            Candidate::Diff(d) => (true, d.to, model.y(d.from)),
+            Candidate::Tau(t) => (false, t.from, model.y(t.to)),
+        };
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, vars)) => vars.push(id),
+            None => groups.push((key, vec![id])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|((diff, id, y), vars)| {
+            // As in `filter_diff`, only live generators count.
+            let taken = if diff {
+                data.in_diffs()[id]
+                    .iter()
+                    .any(|from| model.y(*from) == y && data.generators()[*from].alive())
+            } else {
+                data.out_taus()[id]
+                    .iter()
+                    .any(|to| model.y(*to) == y && data.generators()[*to].alive())
+            };
+            Cardinality {
+                vars,
+                min: 0,
+                max: if taken { 0 } else { 1 },
+            }
+        })
+        .collect()
+}
+
+/// Variables whose facts are visible in the truncation `bot_trunc..=top_trunc`.
+fn vars_in_truncation(model: &E1, candidates: &[Candidate], bot_trunc: i32, top_trunc: i32) -> Vec<usize> {
+    candidates
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| bot_trunc <= c.bot_y(model) && c.top_y(model) <= top_trunc)
+        .map(|(id, _)| id)
+        .collect()
+}
+
+fn issues_to_string(issues: Vec<Issue>) -> String {
+    format!("{issues:?}")
+}
+
+/// Build the problem for one AHSS stem: one theory per RP^n truncation, checked
+/// the same way the automated AHSS solver checks a cell.
+pub fn ahss_stem_problem<'a>(
+    data: &'a SyntheticSS,
+    model: &'a E1,
+    candidates: &'a [Candidate],
+    stem: i32,
+    top_trunc: i32,
+) -> ConstraintProblem<'a> {
+    let mut problem = ConstraintProblem::new(candidates.len());
+    problem.cardinalities = additive_constraints(data, model, candidates);
+
+    let mut truncations: Vec<_> = rp_truncations()
+        .iter()
+        .copied()
+        .filter(|&(_, bt, _)| bt <= top_trunc && bt <= stem + 1)
+        .collect();
+    // Cells above stem + 1 don't influence this stem, so all truncations
+    // RP_bt^tt with tt >= stem + 2 behave the same. Keep the smallest one.
+    truncations.sort();
+    truncations.dedup_by_key(|&mut (synthetic, bt, tt)| (synthetic, bt, tt.min(stem + 2)));
+    truncations.sort_by_key(|&(_, bt, tt)| (tt.min(stem + 1), -bt));
+
+    for (synthetic, bt, tt) in truncations {
+        let check = move |assignment: &[Option<bool>]| {
+            let data = apply_assignment(data, model, candidates, assignment);
+            if synthetic {
+                let (pages, issues) = compute_pages(&data, model, bt, tt, stem, stem, true);
+                let observed = pages.convergence_at_stem(model, stem);
+                compare_synthetic(&observed, synthetic_rp(bt, tt), bt, tt, stem).map_err(issues_to_string)?;
+                if !issues.is_empty() {
+                    return Err(issues_to_string(issues));
+                }
+                compare_algebraic_spectral_sequence(&data, model, &pages, stem, bt, tt, true)
+                    .map_err(issues_to_string)
+            } else {
+                let (pages, issues) = compute_pages(&data, model, bt, tt, stem - 1, stem, true);
+                let observed = pages.algebraic_convergence_at_stem(model, stem);
+                compare_algebraic(&observed, algebraic_rp(bt, tt), bt, tt, stem).map_err(issues_to_string)?;
+                if !issues.is_empty() {
+                    return Err(issues_to_string(issues));
+                }
+                compare_algebraic_spectral_sequence(&data, model, &pages, stem, bt, tt, true)
+                    .map_err(issues_to_string)
+            }
+        };
+        problem.theories.push(Theory {
+            name: format!("RP{bt}_{tt}"),
+            vars: vars_in_truncation(model, candidates, bt, tt),
+            check: Box::new(check),
+        });
+    }
+
+    problem
+}
+
+/// Build the problem for one EHP stem: one theory per sphere, checking the
+/// stable sphere against `S0` and the unstable ones against their algebraic
+/// convergence, as in [`crate::solve::ehp::find_ehp_issues`].
+pub fn ehp_stem_problem<'a>(
+    data: &'a SyntheticSS,
+    model: &'a E1,
+    candidates: &'a [Candidate],
+    stem: i32,
+    top_trunc: i32,
+) -> ConstraintProblem<'a> {
+    let mut problem = ConstraintProblem::new(candidates.len());
+    problem.cardinalities = additive_constraints(data, model, candidates);
+
+    for sphere in 2..=(stem + 2).min(top_trunc + 1) {
+        let check = move |assignment: &[Option<bool>]| {
+            let data = apply_assignment(data, model, candidates, assignment);
+            if sphere - 2 == stem {
+                let (pages, issues) = compute_pages(&data, model, 0, sphere - 1, stem, stem, true);
+                let observed = pages.convergence_at_stem(model, stem);
+                compare_synthetic(&observed, &S0, 0, sphere - 1, stem).map_err(issues_to_string)?;
+                if !issues.is_empty() {
+                    return Err(issues_to_string(issues));
+                }
+                compare_algebraic_spectral_sequence(&data, model, &pages, stem, 0, sphere - 1, false)
+                    .map_err(issues_to_string)
+            } else {
+                let (pages, issues) = compute_pages(&data, model, 0, sphere - 1, stem - 1, stem, true);
+                let observed = pages.algebraic_convergence_at_stem(model, stem);
+                compare_algebraic(&observed, algebraic_spheres(sphere), 0, sphere - 1, stem)
+                    .map_err(issues_to_string)?;
+                if !issues.is_empty() {
+                    return Err(issues_to_string(issues));
+                }
+                compare_algebraic_spectral_sequence(&data, model, &pages, stem, 0, sphere - 1, false)
+                    .map_err(issues_to_string)
+            }
+        };
+        problem.theories.push(Theory {
+            name: format!("S^{sphere}"),
+            vars: vars_in_truncation(model, candidates, 0, sphere - 1),
+            check: Box::new(check),
+        });
+    }
+
+    problem
+}
+
+/// A copy of `data` without the recorded status of `candidates`, so reopened
+/// `Unknown` facts don't shadow the assignment we try.
+pub fn reopen(data: &SyntheticSS, candidates: &[Candidate]) -> SyntheticSS {
+    let mut base = data.clone();
+    for c in candidates {
+        base.forget_unknown(c.edge());
+    }
+    base
+}
+
+/// Solve a stem of the AHSS (`ahss`) or EHP: collect its candidates up to
+/// filtration `top_trunc`, enumerate up to `limit` consistent assignments and
+/// split the candidates into forced and ambiguous ones.
+pub fn solve_stem(
+    data: &SyntheticSS,
+    model: &E1,
+    stem: i32,
+    top_trunc: i32,
+    limit: usize,
+    ahss: bool,
+) -> ConstraintReport {
+    let candidates = stem_candidates(data, model, stem, top_trunc);
+
+    let base = reopen(data, &candidates);
+
+    let mut problem = if ahss {
+        ahss_stem_problem(&base, model, &candidates, stem, top_trunc)
+    } else {
+        ehp_stem_problem(&base, model, &candidates, stem, top_trunc)
+    };
+    problem.max_checks = MAX_CHECKS;
+    let enumeration = problem.enumerate(limit);
+    let backbone = problem.backbone().unwrap_or_default();
+    drop(problem);
+
+    ConstraintReport {
+        stem,
+        candidates,
+        enumeration,
+        backbone,
+    }
+}
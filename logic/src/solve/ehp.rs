@@ -1,15 +1,20 @@
 //! EHP-specific verification: applying the EHP recursion across spheres
 //! ([`apply_ehp_recursively`]), checking each computed stem against the
 //! algebraic convergence and the AHSS ([`find_ehp_issues`]), and a final
-//! sanity check against the classical homotopy-group orders ([`verify_geometric`]).
+//! sanity check against the classical homotopy groups ([`verify_geometric`],
+//! read from [`crate::data::classical`]): their orders, and in the stable range
+//! their group structure, collected in a serializable [`GeometricReport`].
 
 use std::{iter::FilterMap, ops::RangeInclusive};
 
+use serde::{Deserialize, Serialize};
+
 use crate::{
     MAX_STEM, MAX_VERIFY_STEM,
-    data::{curtis::MODEL, r#static::{S0, algebraic_spheres}},
+    data::{classical::ClassicalTable, curtis::MODEL, r#static::{S0, algebraic_spheres}},
     domain::{
         e1::E1,
+        homotopy::{ComputedGroup, homotopy_group},
         model::SyntheticSS,
         process::{compute_pages, ehp_recursion, try_compute_pages},
         ss::SSPages,
@@ -158,86 +163,146 @@ pub fn find_ehp_issues(
 }
 
 
-/// Orders of the classical homotopy groups pi_{stem+k}(S^k), copied from a
-/// spreadsheet. One row per stem (0..=`CLASSICAL_MAX_STEM`), tab-separated, one
-/// column per sphere (column k holds S^{k+1}). A value of `-2` marks an entry
-/// that is not yet known and is skipped during comparison.
-const GEOMETRIC_ORDERS: &str = "\
-2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2
-1	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2
-1	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2
-1	2	12	24	24	24	24	24	24	24	24	24	24	24	24	24	24	24	24	24	24	24	24	24	24	24	24	24	24	24	24	24	24	24	24
-1	12	2	4	2	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1
-1	2	2	4	2	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1
-1	2	3	72	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2	2
-1	3	15	15	30	60	120	240	240	240	240	240	240	240	240	240	240	240	240	240	240	240	240	240	240	240	240	240	240	240	240	240	240	240	240
-1	15	2	2	2	48	8	16	8	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4
-1	2	4	8	8	8	16	32	16	8	8	8	8	8	8	8	8	8	8	8	8	8	8	8	8	8	8	8	8	8	8	8	8	8	8
-1	4	24	2880	144	144	48	1152	48	24	12	6	6	6	6	6	6	6	6	6	6	6	6	6	6	6	6	6	6	6	6	6	6	6	6
-1	24	336	2688	2016	2016	1008	1008	1008	504	504	504	504	504	504	504	504	504	504	504	504	504	504	504	504	504	504	504	504	504	504	504	504	504	504
-1	336	4	64	8	240	1	1	1	12	2	4	2	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1	1
-1	4	6	288	12	6	6	12	6	6	12	12	6	3	3	3	3	3	3	3	3	3	3	3	3	3	3	3	3	3	3	3	3	3	3
-1	6	30	30240	12	24	96	23040	64	32	32	384	32	16	8	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4
-1	30	30	30	60	360	960	3840	1920	960	480	480	960	960	960	960	960	960	960	960	960	960	960	960	960	960	960	960	960	960	960	960	960	960	960
-1	30	12	72	4	2016	16	128	16	480	2	2	2	48	8	16	8	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4	4
-1	12	48	4608	16	16	16	96	16	8	8	16	16	16	32	64	32	16	16	16	16	16	16	16	16	16	16	16	16	16	16	16	16	16	16
-1	48	48	46080	96	288	48	24192	48	96	64	15360	128	128	128	3072	128	64	32	16	16	16	16	16	16	16	16	16	16	16	16	16	16	16	16
-1	48	264	4224	528	8448	528	528	528	1584	2112	8448	2112	2112	1056	1056	1056	528	528	528	528	528	528	528	528	528	528	528	528	528	528	528	528	528	528
-1	264	4	64	24	5760	24	72	24	12096	96	768	192	5760	24	24	24	288	48	96	48	24	24	24	24	24	24	24	24	24	24	24	24	24	24
-1	4	2	32	4	2	4	32	8	8	16	32	32	16	8	16	8	8	16	16	8	4	4	4	4	4	4	4	4	4	4	4	4	4	4
-1	2	2	32	8	32	64	4096	128	64	64	512	64	128	128	2048	128	64	64	256	32	16	8	4	4	4	4	4	4	4	4	4	4	4	4
-1	2	4	32	32	2048	1024	8192	2048	2048	512	512	512	512	2048	8192	2048	1024	512	512	512	256	256	256	256	256	256	256	256	256	256	256	256	256	256
-1	4	8	32	16	512	128	4096	128	1024	32	16	16	128	64	512	64	128	8	4	4	16	8	16	8	4	4	4	4	4	4	4	4	4	4
-1	8	32	2048	128	1024	256	32768	256	64	64	128	32	32	64	128	64	16	8	4	4	4	8	16	8	4	4	4	4	4	4	4	4	4	4
-1	32	32	32768	256	2048	512	540672	512	256	128	8192	128	32	32	256	32	32	32	256	32	32	32	256	32	16	8	4	4	4	4	4	4	4	4
-1	32	32	4096	128	1024	128	3072	128	256	256	512	128	32	8	8	8	8	32	128	32	32	16	16	16	8	8	8	8	8	8	8	8	8	8
-1	32	16	4096	32	512	16	128	32	128	64	256	16	64	2	2	2	16	8	64	16	32	2	2	2	8	4	8	4	2	2	2	2	2	2
-1	16	8	4096	64	64	128	16384	256	512	64	256	16	4	4	16	4	4	8	16	16	8	4	4	2	2	4	4	2	1	1	1	1	1	1
-1	8	8	1024	64	256	512	1048576	1024	1024	128	512	64	256	256	16384	256	64	64	512	64	128	128	1024	64	32	32	128	16	8	4	2	2	2	2
-1	8	16	256	32	1024	128	8192	256	512	32	128	64	1024	2048	8192	2048	1024	256	256	256	256	1024	4096	1024	512	256	256	256	-2	-2	-2	-2	-2	-2
-1	16	16	2048	32	2048	64	4096	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2
-1	16	8	4096	16	128	64	2048	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2	-2";
-
-/// Verify stable stems match expected values
-pub fn verify_geometric(data: &SyntheticSS) {
-    const CLASSICAL_MAX_SPHERE: i32 = 35;
-    const CLASSICAL_MAX_STEM: i32 = 33;
-
-    // Parse the order table into rows (one per stem) of columns (one per sphere).
-    let geometric_gens: Vec<Vec<i32>> = GEOMETRIC_ORDERS
-        .lines()
-        .map(|line| line.split('\t').map(|s| s.parse().unwrap()).collect())
-        .collect();
-
-    for sphere in (1..=CLASSICAL_MAX_SPHERE).into_iter().rev() {
-        let (pages, _) = compute_pages(data, &MODEL, 0, sphere - 1, 0, 256, true);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerifyStatus {
+    Match,
+    Mismatch,
+    /// The classical table does not know the expected value.
+    Unknown,
+    /// The computation does not determine the 2-extensions of the group.
+    Undetermined,
+}
+
+/// The order of π_{sphere+stem}(S^sphere) against the number of τ-free
+/// classes surviving in the EHP truncation of S^sphere.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderCheck {
+    pub sphere: i32,
+    pub stem: i32,
+    /// The order in the classical table (a Z counted as 2).
+    pub expected_order: Option<u64>,
+    /// The number of surviving classes the expected order accounts for.
+    pub expected_count: Option<u32>,
+    pub observed_count: u32,
+    pub status: VerifyStatus,
+    /// The surviving classes.
+    pub generators: Vec<String>,
+}
+
+/// The stable stem `stem` against the group assembled on S^sphere, for every
+/// sphere in the stable range of the stem.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StableCheck {
+    pub stem: i32,
+    pub sphere: i32,
+    pub expected: String,
+    pub observed: String,
+    pub status: VerifyStatus,
+    /// Whether the groups have the same order, i.e. only a 2-extension is off.
+    pub same_order: bool,
+    pub generators: Vec<String>,
+}
 
-        let mut conv_gens = vec![0; (CLASSICAL_MAX_STEM + 1) as usize];
+/// The result of [`verify_geometric`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GeometricReport {
+    /// The stems above this were not checked.
+    pub max_stem: i32,
+    pub orders: Vec<OrderCheck>,
+    pub stable: Vec<StableCheck>,
+}
+
+impl GeometricReport {
+    pub fn passed(&self) -> bool {
+        self.orders.iter().all(|c| c.status != VerifyStatus::Mismatch)
+            && self.stable.iter().all(|c| c.status != VerifyStatus::Mismatch)
+    }
 
+    pub fn print_mismatches(&self) {
+        for c in self.orders.iter().filter(|c| c.status == VerifyStatus::Mismatch) {
+            eprintln!(
+                "Geometric homotopy groups on the {} Sphere do not agree on stem {}. Expect: {}, Got: {}",
+                c.sphere,
+                c.stem,
+                c.expected_count.unwrap(),
+                c.observed_count
+            );
+        }
+        for c in self.stable.iter().filter(|c| c.status == VerifyStatus::Mismatch) {
+            let hint = if c.same_order { " (same order, missing a 2-extension?)" } else { "" };
+            eprintln!("The stable stem {} does not agree. Expect: {}, Got: {}{hint}", c.stem, c.expected, c.observed);
+        }
+        let undetermined = self.stable.iter().filter(|c| c.status == VerifyStatus::Undetermined).count();
+        if undetermined > 0 {
+            println!("{undetermined} stable groups are only determined up to extension");
+        }
+    }
+}
+
+/// Verify the computed homotopy groups against the classical ones in `table`:
+/// the orders of every π_{n+k}(S^n) in it, and the group structure of every
+/// stable stem the table has on every sphere of the table where it is stable
+/// (where the computation determines the group, see
+/// [`crate::domain::homotopy`]). Stems beyond `MAX_VERIFY_STEM` are not
+/// checked.
+pub fn verify_geometric(data: &SyntheticSS, table: &ClassicalTable) -> GeometricReport {
+    let max_stem = table.max_stem().min(MAX_VERIFY_STEM);
+    let mut report = GeometricReport { max_stem, ..Default::default() };
+
+    for sphere in 1..=table.max_sphere() {
+        let (pages, _) = compute_pages(data, &MODEL, 0, sphere - 1, 0, 256, true);
+
+        let mut conv_gens = vec![vec![]; (max_stem + 1) as usize];
         for (id, g) in MODEL.enumerate() {
-            if pages.element_in_pages(id) {
-                if pages.element_final(id).1.free() {
-                    if g.stem <= CLASSICAL_MAX_STEM {
-                        conv_gens[g.stem as usize] += 1;
-                    }
-                }
+            if g.stem <= max_stem && pages.element_in_pages(id) && pages.element_final(id).1.free() {
+                conv_gens[g.stem as usize].push(MODEL.name(id).to_string());
             }
         }
 
-        let geometric_order: Vec<_> = (0..=CLASSICAL_MAX_STEM)
-            .into_iter()
-            .map(|x| geometric_gens[x as usize][(sphere - 1) as usize].trailing_zeros())
-            .collect();
-
-        for stem in 0..=CLASSICAL_MAX_STEM {
-            if geometric_order[stem as usize] != conv_gens[stem as usize] {
-                if geometric_gens[stem as usize][(sphere - 1) as usize] != -2 {
-                    eprintln!(
-                        "Geometric homotopy groups on the {sphere} Sphere do not agree on stem {stem}. Expect: {}, Got: {}",
-                        geometric_order[stem as usize], conv_gens[stem as usize]
-                    );
-                }
-            }
+        for stem in 0..=max_stem {
+            let generators = &conv_gens[stem as usize];
+            let expected_order = table.order(sphere, stem);
+            let expected_count = expected_order.map(u64::trailing_zeros);
+            let observed_count = generators.len() as u32;
+            let status = match expected_count {
+                None => VerifyStatus::Unknown,
+                Some(c) if c == observed_count => VerifyStatus::Match,
+                Some(_) => VerifyStatus::Mismatch,
+            };
+            report.orders.push(OrderCheck {
+                sphere,
+                stem,
+                expected_order,
+                expected_count,
+                observed_count,
+                status,
+                generators: generators.clone(),
+            });
+        }
+
+        // Every stem that is stable on this sphere.
+        for stem in 0..=(sphere - 2).min(max_stem) {
+            let Some(expected) = table.stable_group(stem) else {
+                continue;
+            };
+            let group = homotopy_group(data, &MODEL, &pages, sphere, stem);
+            let status = match &group {
+                ComputedGroup::Known(group) if *group == expected => VerifyStatus::Match,
+                ComputedGroup::Known(_) => VerifyStatus::Mismatch,
+                ComputedGroup::UnknownExtension { .. } => VerifyStatus::Undetermined,
+            };
+            report.stable.push(StableCheck {
+                stem,
+                sphere,
+                expected: expected.to_string(),
+                observed: group.to_string(),
+                status,
+                same_order: group.free() == expected.free
+                    && group.torsion_valuation() == expected.torsion_valuation(),
+                generators: conv_gens[stem as usize].clone(),
+            });
         }
     }
+    report
 }
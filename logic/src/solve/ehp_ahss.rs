@@ -26,16 +26,16 @@ pub fn set_metastable_range(
     // E1-page torsion of every metastable generator.
     for (idx, g) in STABLE_MODEL.gens().iter().enumerate() {
         if in_metastable_range(g.y, g.stem) {
-            ehp.set_generator(&MODEL, &g.name, ahss.generators[idx])?;
+            ehp.set_generator(&MODEL, &g.name, ahss.generators()[idx])?;
         }
     }
     // Differentials whose target is metastable.
-    for ds in &ahss.diffs_page {
+    for ds in ahss.diffs_page() {
         for d in ds {
             let g_from = &STABLE_MODEL.get(d.from);
             let g_to = &STABLE_MODEL.get(d.to);
             if in_metastable_range(g_to.y, g_to.stem) {
-                let (kind, proof) = ahss.from_to.get(&(d.from, d.to)).expect("If there is no reference to a proof here (note that the string can still be empty), then inserting differentials not done carefully enough.");
+                let (kind, proof) = ahss.facts().get(&(d.from, d.to)).expect("If there is no reference to a proof here (note that the string can still be empty), then inserting differentials not done carefully enough.");
                 ehp.add_diff_name(
                     &MODEL,
                     g_from.name.clone(),
@@ -48,12 +48,12 @@ pub fn set_metastable_range(
     }
 
     // Internal tau-multiplications whose target is metastable.
-    for (page, ts) in ahss.internal_tau_page.iter().enumerate() {
+    for (page, ts) in ahss.internal_tau_page().iter().enumerate() {
         for t in ts {
             let g_from = &STABLE_MODEL.get(t.from);
             let g_to = &STABLE_MODEL.get(t.to);
             if in_metastable_range(g_to.y, g_to.stem) {
-                let (kind, proof) = ahss.from_to.get(&(t.from, t.to)).expect("If there is no reference to a proof here (note that internally it can still have no proof), then inserting internal tau's not done carefully enough.");
+                let (kind, proof) = ahss.facts().get(&(t.from, t.to)).expect("If there is no reference to a proof here (note that internally it can still have no proof), then inserting internal tau's not done carefully enough.");
                 ehp.add_int_tau_name(
                     &MODEL,
                     g_from.name.clone(),
@@ -67,25 +67,19 @@ pub fn set_metastable_range(
     }
 
     // External tau-multiplications whose target is metastable.
-    for esss in &ahss.external_tau_page {
-        for ess in esss {
-            for es in ess {
-                for e in es {
-                    let g_from = &STABLE_MODEL.get(e.from);
-                    let g_to = &STABLE_MODEL.get(e.to);
-                    if in_metastable_range(g_to.y, g_to.stem) {
-                        let (kind, proof) = ahss.from_to.get(&(e.from, e.to)).expect("If there is no reference to a proof here (note that internally it can still have no proof), then inserting external tau's not done carefully enough.");
-                        ehp.add_ext_tau_name(
-                            &MODEL,
-                            g_from.name.clone(),
-                            g_to.name.clone(),
-                            e.af,
-                            proof.clone().map(|x| format!("(Metastable) - {x}")),
-                            *kind, // TODO! <<
-                        )?;
-                    }
-                }
-            }
+    for e in ahss.ext_taus() {
+        let g_from = &STABLE_MODEL.get(e.from);
+        let g_to = &STABLE_MODEL.get(e.to);
+        if in_metastable_range(g_to.y, g_to.stem) {
+            let (kind, proof) = ahss.facts().get(&(e.from, e.to)).expect("If there is no reference to a proof here (note that internally it can still have no proof), then inserting external tau's not done carefully enough.");
+            ehp.add_ext_tau_name(
+                &MODEL,
+                g_from.name.clone(),
+                g_to.name.clone(),
+                e.af,
+                proof.clone().map(|x| format!("(Metastable) - {x}")),
+                *kind, // TODO! <<
+            )?;
         }
     }
 
@@ -120,7 +114,7 @@ fn check(
     let mut issues = vec![];
 
     // We check if every AHSS diff between known generators also exists on EHP
-    for (&(from, to), (kind, _)) in &a.from_to {
+    for (&(from, to), (kind, _)) in a.facts() {
         // Skip Algebraic things
         // This must already have been commutative
         // Else the algebraic data was wrong, which i don't assume
@@ -161,7 +155,7 @@ fn check(
                         let to_g_b = b_p.element_at_page(d_y + 1, b_to);
 
                         if from_g_b.1.alive() && to_g_b.1.alive() {
-                            if let Some((kind, _)) = b.from_to.get(&(b_from, b_to)) && (*kind == Kind::Algebraic || *kind == Kind::Real) {
+                            if let Some((kind, _)) = b.facts().get(&(b_from, b_to)) && (*kind == Kind::Algebraic || *kind == Kind::Real) {
                                 issues.push(Issue::InvalidEHPAHSSMap {
                                     name: from_name,
                                     from_torsion: from_g_b.1,
@@ -206,8 +200,8 @@ pub fn compare_ehp_ahss(
     for y in 0..=(sphere - 1) {
         for &ehp_id in MODEL.gens_id_in_stem_y(stem, y) {
             if let Some(ahss_id) = ehp_ahss[ehp_id] {
-                if ehp.generators[ehp_id].alive() {
-                    if ehp.generators[ehp_id] > ahss.generators[ahss_id] {
+                if ehp.generators()[ehp_id].alive() {
+                    if ehp.generators()[ehp_id] > ahss.generators()[ahss_id] {
                         issues.push(Issue::InvalidEHPAHSSGen {
                             name: MODEL.name(ehp_id).to_string(),
                             stem,
@@ -0,0 +1,183 @@
+//! Enumerate mode: instead of the single answer the solvers commit to, list
+//! every combination of a stem's undetermined (`Unknown`) differentials and
+//! taus that is consistent with all reference data, together with the homotopy
+//! group orders each combination produces. The consistency checks are the
+//! theories of [`crate::solve::constraint`].
+
+use crate::{
+    data::r#static::rp_truncations,
+    domain::{
+        e1::E1,
+        model::{Diff, ExtTauMult, SyntheticSS},
+        process::compute_pages,
+    },
+    solve::constraint::{Candidate, ahss_stem_problem, apply_assignment, ehp_stem_problem, reopen},
+    types::Kind,
+};
+
+/// One consistent world: the value of every undetermined fact and the
+/// (2-primary) orders it gives, as `(truncation, stem, log_2 order)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Configuration {
+    pub assignment: Vec<bool>,
+    pub orders: Vec<(String, i32, usize)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EnumerationReport {
+    pub stem: i32,
+    pub candidates: Vec<Candidate>,
+    pub configurations: Vec<Configuration>,
+    /// False if we stopped at the limit.
+    pub complete: bool,
+    /// Why there is no consistent configuration, if there is none.
+    pub refutation: Option<String>,
+}
+
+impl EnumerationReport {
+    /// Whether all consistent configurations agree on every order.
+    pub fn orders_determined(&self) -> bool {
+        self.configurations.windows(2).all(|w| w[0].orders == w[1].orders)
+    }
+
+    pub fn print(&self, model: &E1) {
+        println!(
+            "Stem {}: {} undetermined facts, {} consistent configurations{}",
+            self.stem,
+            self.candidates.len(),
+            self.configurations.len(),
+            if self.complete { "" } else { " (limit reached)" }
+        );
+        if self.configurations.is_empty() {
+            println!(
+                "No consistent configuration: {}",
+                self.refutation.as_deref().unwrap_or("unknown")
+            );
+            return;
+        }
+
+        for (i, conf) in self.configurations.iter().enumerate() {
+            println!("\nConfiguration {}:", i + 1);
+            for (c, value) in self.candidates.iter().zip(&conf.assignment) {
+                let verdict = if *value { "Real" } else { "Fake" };
+                println!("  {verdict}: {}", c.describe(model));
+            }
+            for (trunc, stem, order) in &conf.orders {
+                println!("  pi_{stem}({trunc}) has order 2^{order}");
+            }
+        }
+
+        if self.orders_determined() {
+            println!("\nAll configurations give the same orders.");
+        } else {
+            println!("\nThe orders depend on the configuration.");
+        }
+    }
+}
+
+/// The facts recorded as `Unknown` that involve `stem`: differentials into it
+/// and external taus within it.
+pub fn unknown_candidates(data: &SyntheticSS, model: &E1, stem: i32) -> Vec<Candidate> {
+    let mut candidates: Vec<_> = data
+        .facts()
+        .iter()
+        .filter(|(_, (kind, _))| *kind == Kind::Unknown)
+        .filter_map(|(&(from, to), _)| {
+            if model.stem(to) != stem {
+                None
+            } else if model.stem(from) == stem + 1 {
+                Some(Candidate::Diff(Diff { from, to }))
+            } else if model.stem(from) == stem {
+                Some(Candidate::Tau(ExtTauMult {
+                    from,
+                    to,
+                    af: model.af(to) + 1,
+                }))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    // Same order as `stem_candidates`, so small truncations prune early.
+    candidates.sort_by_key(|c| {
+        let (from, to) = c.edge();
+        (model.y(from), -model.y(to), from, to)
+    });
+    candidates
+}
+
+/// The truncations we report orders for: the spheres S^1 up to the stable
+/// range for the EHP, the stunted projective spaces RP_1^n for the AHSS.
+fn order_truncations(stem: i32, ahss: bool) -> Vec<(String, i32, i32)> {
+    if ahss {
+        let mut truncs: Vec<_> = rp_truncations()
+            .iter()
+            .filter(|&&(synthetic, bt, tt)| synthetic && bt == 1 && tt <= stem + 3)
+            .map(|&(_, bt, tt)| (format!("RP{bt}_{tt}"), bt, tt))
+            .collect();
+        truncs.sort_by_key(|t| t.2);
+        truncs.dedup();
+        truncs
+    } else {
+        (1..=stem + 3)
+            .map(|sphere| (format!("S^{sphere}"), 0, sphere - 1))
+            .collect()
+    }
+}
+
+/// The log_2 orders of `stem` and `stem + 1` in every reported truncation:
+/// every surviving tau-free generator contributes a factor of 2.
+fn orders(data: &SyntheticSS, model: &E1, stem: i32, ahss: bool) -> Vec<(String, i32, usize)> {
+    let mut orders = vec![];
+    for (name, bt, tt) in order_truncations(stem, ahss) {
+        let (pages, _) = compute_pages(data, model, bt, tt, stem, stem + 1, true);
+        for s in [stem, stem + 1] {
+            let free = model
+                .gens_id_in_stem(s)
+                .iter()
+                .filter(|id| pages.try_element_final(**id).is_some_and(|el| el.1.free()))
+                .count();
+            // The EHP is indexed by the stem of the sphere, pi_{s + n}(S^n).
+            let degree = if ahss { s } else { s + tt + 1 };
+            orders.push((name.clone(), degree, free));
+        }
+    }
+    orders
+}
+
+/// Enumerate (up to `limit`) the consistent configurations of the `Unknown`
+/// facts in `stem` of the AHSS (`ahss`) or EHP, with their homotopy orders.
+pub fn enumerate_stem(data: &SyntheticSS, model: &E1, stem: i32, limit: usize, ahss: bool) -> EnumerationReport {
+    let candidates = unknown_candidates(data, model, stem);
+    let base = reopen(data, &candidates);
+
+    let problem = if ahss {
+        ahss_stem_problem(&base, model, &candidates, stem, stem + 1)
+    } else {
+        ehp_stem_problem(&base, model, &candidates, stem, stem + 1)
+    };
+    let enumeration = problem.enumerate(limit);
+    drop(problem);
+
+    let configurations = enumeration
+        .models
+        .into_iter()
+        .map(|assignment| {
+            let partial: Vec<_> = assignment.iter().map(|a| Some(*a)).collect();
+            let data = apply_assignment(&base, model, &candidates, &partial);
+            Configuration {
+                orders: orders(&data, model, stem, ahss),
+                assignment,
+            }
+        })
+        .collect();
+
+    EnumerationReport {
+        stem,
+        candidates,
+        configurations,
+        complete: enumeration.complete,
+        refutation: enumeration.refutation,
+    }
+}
@@ -99,7 +99,7 @@ pub fn get_a_diff(data: &SyntheticSS, model: &E1, top_trunc: i32, bot_trunc: i32
                             }
                         }
 
-                        if !data.from_to.contains_key(&(s_id, t_id)) {
+                        if !data.facts().contains_key(&(s_id, t_id)) {
                             if t_torsion.can_map_with_coeff(&s_torsion, coeff) {
                                 return Some(Diff {
                                     from: s_id,
@@ -134,10 +134,10 @@ pub fn get_a_tau(
                 for &t_id in model.gens_id_in_stem(stem) {
                     if let Some((t_af, t_torsion)) = elements.try_element_final(t_id)
                         && t_torsion.alive()
-                        && !data.from_to.contains_key(&(s_id, t_id))
+                        && !data.facts().contains_key(&(s_id, t_id))
                     {
                         let y = model.y(t_id);
-                        if !data.out_taus[s_id].iter().any(|to| model.y(*to) == y) {
+                        if !data.out_taus()[s_id].iter().any(|to| model.y(*to) == y) {
                             if let Some(tau) =
                                 make_ext_tau(model, s_id, s_af, s_torsion, t_id, t_af, t_torsion)
                             {
@@ -169,10 +169,10 @@ pub fn get_a_tau_for_t_ids(
                     && s_torsion.alive()
                 {
                     if let Some(s_torsion) = s_torsion.0 {
-                        if !data.from_to.contains_key(&(s_id, t_id)) {
+                        if !data.facts().contains_key(&(s_id, t_id)) {
                             let y = model.y(t_id);
-                            if !data.out_taus[s_id].iter().any(|to| {
-                                model.y(*to) == y && data.generators[*to].alive()
+                            if !data.out_taus()[s_id].iter().any(|to| {
+                                model.y(*to) == y && data.generators()[*to].alive()
                             }) {
                                 if let Some(tau) =
                                     make_ext_tau(model, s_id, s_af, s_torsion, t_id, t_af, t_torsion)
@@ -206,9 +206,9 @@ pub fn get_a_tau_for_t_ids_s_ids(
                     && s_torsion.alive()
                 {
                     if let Some(s_torsion) = s_torsion.0 {
-                        if !data.from_to.contains_key(&(s_id, t_id)) {
+                        if !data.facts().contains_key(&(s_id, t_id)) {
                             let y = model.y(t_id);
-                            if !data.out_taus[s_id].iter().any(|to| model.y(*to) == y) {
+                            if !data.out_taus()[s_id].iter().any(|to| model.y(*to) == y) {
                                 if let Some(tau) =
                                     make_ext_tau(model, s_id, s_af, s_torsion, t_id, t_af, t_torsion)
                                 {
@@ -118,6 +118,39 @@ pub enum Issue {
         stem: i32,
         sphere: i32,
     },
+
+    /// The sphere's reference data mod τ (every class, with the sources of
+    /// Adams differentials) does not have the dimension of Ext.
+    TauReduction {
+        stem: i32,
+        af: i32,
+
+        expected: usize,
+        observed: usize,
+    },
+
+    /// The τ-free classes of the sphere's reference data in a stem do not
+    /// detect a group of the order of the stable stem (`expected` is the
+    /// 2-adic valuation of its order).
+    TauInversion {
+        stem: i32,
+
+        expected: u32,
+        observed: usize,
+    },
+
+    /// A class of the sphere's reference data in (`stem`, `af`) that is not
+    /// the source of an Adams differential, whose product with h_i is
+    /// nonzero, but that product is dead or less τ-torsion than the class
+    /// (`expected` is the torsion of the class, `observed` of the product).
+    TauLinearity {
+        stem: i32,
+        af: i32,
+        i: u32,
+
+        expected: Torsion,
+        observed: Torsion,
+    },
 }
 
 pub fn compare_synthetic(
@@ -231,20 +264,20 @@ pub fn compare_algebraic_spectral_sequence(
     // At least for EHP this is important
 
     for &from in model.gens_id_in_stem(stem + 1) {
-        let tos = &data.out_diffs[from];
+        let tos = &data.out_diffs()[from];
         for &to in tos {
             // Only differentials landing in this stem, within the truncation window.
             if model.stem(to) == stem
                 && bot_trunc <= model.y(to)
                 && model.y(from) <= top_trunc
             {
-                let alg = data.from_to.get(&(from, to)).unwrap().0 == Kind::Algebraic;
+                let alg = data.facts().get(&(from, to)).unwrap().0 == Kind::Algebraic;
                 if alg {
                     // Algebraic differential whose target is dead but source alive:
                     // the source must already be dead by the differential's page,
                     // otherwise the algebraic structure has been violated.
-                    if !data.generators[to].alive()
-                    && data.generators[from].alive()
+                    if !data.generators()[to].alive()
+                    && data.generators()[from].alive()
                     {
                         let page = model.y(from) - model.y(to);
                         // From should die before the corresponding page.
@@ -264,7 +297,7 @@ pub fn compare_algebraic_spectral_sequence(
                     // Non-algebraic differential that, at its page, still looks like
                     // a length-1 map out of the original AF onto a target that is
                     // alive algebraically — i.e. it should have been algebraic.
-                    if data.generators[from].alive() {
+                    if data.generators()[from].alive() {
                         let coeff = model.af(to) - model.af(from);
                         let page = model.y(from) - model.y(to);
                         let (af, tor) = pages.element_at_page(page, from);
@@ -10,18 +10,31 @@
 //! - [`generate`] / [`solve`]: proposing candidate differentials and taus, and
 //!   auto-deducing forced solutions to issues.
 //! - [`search`]: the parallel speculative branch-and-bound primitives.
-//! - [`automated_common`]: logic shared by the two automated solvers.
+//! - [`constraint`]: an alternative backend that enumerates every consistent
+//!   assignment of a stem's undecided facts instead of branching on one.
+//! - [`enumerate`]: lists every consistent configuration of a stem's `Unknown`
+//!   facts with the homotopy orders each one gives.
+//! - [`automated`]: logic shared by the automated solvers.
 //! - [`automated_ahss`] / [`automated_ehp`]: the automated solvers that drive
 //!   the search to fill in the AHSS and EHP sequences.
+//! - [`automated_cw`]: the same search for the AHSS of a CW spectrum
+//!   described in a file.
+//! - [`tau_bockstein`]: checks the sphere's reference data mod τ against Ext,
+//!   with τ inverted against the stable stems and its τ-exponents against the
+//!   h_i-products of Ext.
 
 pub mod action;
 pub mod ahss;
 pub mod automated_ahss;
 pub mod automated;
+pub mod automated_cw;
 pub mod automated_ehp;
+pub mod constraint;
 pub mod ehp;
 pub mod ehp_ahss;
+pub mod enumerate;
 pub mod generate;
 pub mod issues;
 pub mod search;
 pub mod solve;
+pub mod tau_bockstein;
@@ -0,0 +1,105 @@
+//! A cross-check of the synthetic comparison data of the sphere (`S0`) at both
+//! ends of its τ-Bockstein spectral sequence, against data that does not come
+//! from Lin's program: [`find_tau_bockstein_issues`].
+//!
+//! For the F_2-synthetic sphere, the cofiber of τ is Ext over the Steenrod
+//! algebra and inverting τ gives the classical stable stems. So reducing the
+//! data mod τ, every class (τ-free, τ-torsion, or the source of an Adams
+//! differential) has to be a class of Ext, computed here by
+//! [`crate::data::resolution`]. Inverting τ, the τ-free classes in a stem have
+//! to detect a group of the order of π_stem^s in `classical_homotopy_groups.json`.
+//!
+//! In between, the τ-exponents are checked against the products of Ext
+//! ([`find_tau_linearity_issues`]): Adams differentials are h_i-linear, so if
+//! x is not the source of one, neither is h_i x, and if x is hit by a d_r,
+//! h_i x is zero on E_{r+1}, so it is hit by a d_r' with r' <= r. Where x and
+//! h_i x are alone in their bidegrees, h_i x is then at least as τ-torsion as
+//! x.
+//!
+//! None of this is the comparison with the algebraic Novikov E2 page, which is
+//! not implemented. That page is the cofiber of τ of the C-motivic sphere,
+//! whose τ-torsion comes from Adams–Novikov differentials and does not
+//! describe the same τ-modules as `S0` (see `TODO.md`).
+
+use std::collections::HashMap;
+
+use crate::{
+    data::{classical::ClassicalTable, resolution::Resolution},
+    solve::issues::Issue,
+    types::Torsion,
+};
+
+/// Check `data`, the comparison data of the sphere with the sources of Adams
+/// differentials kept as dead classes (`S0_ZEROES`), in `stem`: mod τ against
+/// `ext` (dim Ext^{s,t} by (stem, s)) for `s <= max_s`, and τ inverted against
+/// the stable stem in `table` if it is known. Stem 0, where π_0^s is Z, is
+/// only checked mod τ.
+pub fn find_tau_bockstein_issues(
+    data: &HashMap<(i32, i32), Vec<Torsion>>,
+    ext: &HashMap<(i32, i32), usize>,
+    table: &ClassicalTable,
+    max_s: i32,
+    stem: i32,
+) -> Result<(), Vec<Issue>> {
+    let mut issues = vec![];
+
+    for af in 0..=max_s {
+        let observed = data.get(&(stem, af)).map_or(0, Vec::len);
+        let expected = ext.get(&(stem, af)).copied().unwrap_or(0);
+        if observed != expected {
+            issues.push(Issue::TauReduction { stem, af, expected, observed });
+        }
+    }
+
+    if let Some(group) = table.stable_group(stem)
+        && stem != 0
+    {
+        let observed = data
+            .iter()
+            .filter(|&(&(s, _), _)| s == stem)
+            .map(|(_, torsions)| torsions.iter().filter(|t| t.free()).count())
+            .sum();
+        let expected = group.torsion_valuation();
+        if observed != expected as usize {
+            issues.push(Issue::TauInversion { stem, expected, observed });
+        }
+    }
+
+    if issues.is_empty() { Ok(()) } else { Err(issues) }
+}
+
+/// Check the τ-exponents of `data` (as in [`find_tau_bockstein_issues`]) in
+/// `stem` against multiplication by h_0, .., h_3 in `resolution`, whose Ext
+/// is `ext`, for `s < max_s`: where a class and its nonzero product with h_i
+/// are alone in their bidegrees and the class is not the source of an Adams
+/// differential, the product has to be at least as τ-torsion as the class.
+pub fn find_tau_linearity_issues(
+    data: &HashMap<(i32, i32), Vec<Torsion>>,
+    resolution: &Resolution,
+    ext: &HashMap<(i32, i32), usize>,
+    max_s: i32,
+    stem: i32,
+) -> Result<(), Vec<Issue>> {
+    let alone = |key: (i32, i32)| match data.get(&key).map(Vec::as_slice) {
+        Some(&[t]) if ext.get(&key) == Some(&1) => Some(t),
+        _ => None,
+    };
+    let mut issues = vec![];
+
+    for af in 0..max_s {
+        let Some(expected) = alone((stem, af)).filter(Torsion::alive) else {
+            continue;
+        };
+        for i in 0..4 {
+            let Some(observed) = alone((stem + (1 << i) - 1, af + 1)) else {
+                continue;
+            };
+            let nonzero = resolution.h_product(i, stem, af).is_some_and(|p| !p[0].is_empty());
+            if nonzero && !(observed.alive() && observed >= expected) {
+                issues.push(Issue::TauLinearity { stem, af, i, expected, observed });
+            }
+        }
+    }
+
+    if issues.is_empty() { Ok(()) } else { Err(issues) }
+}
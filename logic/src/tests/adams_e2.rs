@@ -0,0 +1,55 @@
+//! The parser for the Adams E2 CSVs: the quirks of Lin's format, the
+//! comparison data it gives and errors pointing at the offending line.
+
+use crate::{data::adams_e2::{AdamsE2, Fate}, types::Torsion};
+
+const HEADER: &str = "\u{feff}stem,s,base,diff,level\n";
+
+#[test]
+fn lin_format() {
+    let csv = format!(
+        "{HEADER}0,0,0,\"\",9000\n1,1,0,[NULL],9000\n15,1,0,0,9998\n14,3,\"0,1\",0,2\n14,3,1,[NULL],9997\n"
+    );
+    let e2 = AdamsE2::parse(&csv).unwrap();
+    let fates: Vec<_> = e2.rows.iter().map(|r| (r.line, r.fate, r.diff.is_some())).collect();
+    assert_eq!(
+        fates,
+        [
+            (2, Fate::Permanent, false),
+            (3, Fate::Permanent, false),
+            (4, Fate::Source { r: 2 }, true),
+            (5, Fate::Target { r: 2 }, true),
+            (6, Fate::Source { r: 3 }, false),
+        ]
+    );
+    assert_eq!(e2.rows[3].base, [0, 1]);
+
+    let torsions = e2.torsions(0, true, true, &[]);
+    assert_eq!(torsions[&(15, 2)], [Torsion::zero()]);
+    assert_eq!(torsions[&(14, 4)], [Torsion::default(), Torsion::new(1)]);
+    assert!(!e2.torsions(0, true, false, &[]).contains_key(&(15, 2)));
+
+    // A free cell loses one class in every AF of its stem.
+    let torsions = e2.torsions(1, false, false, &[1]);
+    assert!(!torsions.contains_key(&(1, 0)));
+    assert_eq!(torsions[&(2, 1)], [Torsion::default()]);
+    assert_eq!(torsions[&(15, 3)].len(), 2);
+}
+
+#[test]
+fn columns_are_found_by_name() {
+    let e2 = AdamsE2::parse("level,stem,s,diff,base\n9000,3,1,[NULL],0\n").unwrap();
+    assert_eq!((e2.rows[0].stem, e2.rows[0].af), (3, 1));
+}
+
+#[test]
+fn errors_name_the_line() {
+    let err = |csv: &str| AdamsE2::parse(&format!("{HEADER}0,0,0,[NULL],9000\n{csv}")).unwrap_err();
+    assert!(err("1,x,0,[NULL],9000\n").starts_with("3: s should be a number"));
+    assert!(err("1,1,0,[NULL]\n").starts_with("3: there is no level column"));
+    assert!(err("1,1,\"0,1,0,[NULL],9000\n").starts_with("3: unterminated quote"));
+    assert!(err("1,1,0,[NULL],7\n1,1,0,[NULL],9999\n").starts_with("4: 9999 is not a level"));
+    // A d_2 out of (15, 1) needs a target in (14, 3).
+    assert!(err("15,1,0,0,9998\n").starts_with("3: 1 d_2 into stem 14, s 3 have no target"));
+    assert!(AdamsE2::parse("stem,s,base,level\n").unwrap_err().starts_with("1: there is no column diff"));
+}
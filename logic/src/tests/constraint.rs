@@ -0,0 +1,222 @@
+//! The solver of [`crate::solve::constraint`] on small hand-built problems
+//! (propagation, backjumping, the backbone, the check budget) and against a
+//! brute-force enumeration on random ones.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::solve::constraint::{Backbone, Cardinality, ConstraintProblem, Theory};
+
+const RANDOM_CASES: u64 = 500;
+
+fn theory<'a>(
+    name: &str,
+    vars: &[usize],
+    check: impl Fn(&[bool]) -> bool + Sync + 'a,
+) -> Theory<'a> {
+    let own = vars.to_vec();
+    Theory {
+        name: name.to_string(),
+        vars: vars.to_vec(),
+        check: Box::new(move |assignment| {
+            // The solver only shows a theory its own variables.
+            for (v, a) in assignment.iter().enumerate() {
+                assert_eq!(a.is_some(), own.contains(&v), "variable {v} leaked into the check");
+            }
+            let values: Vec<bool> = own.iter().map(|v| assignment[*v].unwrap()).collect();
+            if check(&values) { Ok(()) } else { Err("refuted".to_string()) }
+        }),
+    }
+}
+
+fn models(bits: &[&str]) -> Vec<Vec<bool>> {
+    bits.iter().map(|m| m.chars().map(|c| c == '1').collect()).collect()
+}
+
+#[test]
+fn cardinality_propagation() {
+    let mut problem = ConstraintProblem::new(3);
+    problem.cardinalities.push(Cardinality {
+        vars: vec![0, 1, 2],
+        min: 1,
+        max: 1,
+    });
+    let enumeration = problem.enumerate(usize::MAX);
+    assert!(enumeration.complete);
+    assert_eq!(enumeration.models, models(&["001", "010", "100"]));
+
+    let limited = problem.enumerate(2);
+    assert!(!limited.complete);
+    assert_eq!(limited.models, models(&["001", "010"]));
+
+    let fixed = problem.enumerate_with(&[(1, false)], usize::MAX);
+    assert_eq!(fixed.models, models(&["001", "100"]));
+}
+
+#[test]
+fn backbone_forced_and_ambiguous() {
+    // x0 is refuted by a theory, so the cardinality forces x1; x2 and x3 are
+    // free but x3 implies x2.
+    let mut problem = ConstraintProblem::new(4);
+    problem.cardinalities.push(Cardinality {
+        vars: vec![0, 1],
+        min: 1,
+        max: 1,
+    });
+    problem.theories.push(theory("no x0", &[0], |v| !v[0]));
+    problem.theories.push(theory("x3 -> x2", &[2, 3], |v| v[0] || !v[1]));
+
+    assert_eq!(problem.enumerate(usize::MAX).models, models(&["0100", "0110", "0111"]));
+    assert_eq!(
+        problem.backbone(),
+        Some(Backbone {
+            forced: vec![(0, false), (1, true)],
+            ambiguous: vec![2, 3],
+            undecided: vec![],
+        })
+    );
+}
+
+#[test]
+fn infeasible_core() {
+    // x3 and x4 must both hold, which the theory forbids; x0..x2 play no part.
+    let mut problem = ConstraintProblem::new(5);
+    problem.cardinalities.push(Cardinality {
+        vars: vec![3, 4],
+        min: 2,
+        max: 2,
+    });
+    problem.theories.push(theory("core", &[3, 4], |v| !(v[0] && v[1])));
+
+    let enumeration = problem.enumerate(usize::MAX);
+    assert!(enumeration.complete);
+    assert!(enumeration.models.is_empty());
+    assert_eq!(enumeration.refutation.as_deref(), Some("core: refuted"));
+    assert_eq!(problem.backbone(), None);
+}
+
+#[test]
+fn backjumping_skips_irrelevant_decisions() {
+    // The conflict only involves x3 and x4, so after refuting them under the
+    // first values of x0..x2 the search must not try the other seven.
+    let prefixes = AtomicUsize::new(0);
+    let mut problem = ConstraintProblem::new(5);
+    problem.theories.push(theory("prefix", &[0, 1, 2], |_| {
+        prefixes.fetch_add(1, Ordering::Relaxed);
+        true
+    }));
+    problem.theories.push(theory("core", &[3, 4], |_| false));
+
+    let enumeration = problem.enumerate(usize::MAX);
+    assert!(enumeration.complete);
+    assert!(enumeration.models.is_empty());
+    assert_eq!(prefixes.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn budget_leaves_undecided() {
+    let mut problem = ConstraintProblem::new(2);
+    problem.theories.push(theory("only 00", &[0, 1], |v| !v[0] && !v[1]));
+    problem.max_checks = 1;
+    assert_eq!(
+        problem.backbone(),
+        Some(Backbone {
+            forced: vec![],
+            ambiguous: vec![],
+            undecided: vec![0, 1],
+        })
+    );
+
+    problem.max_checks = usize::MAX;
+    assert_eq!(
+        problem.backbone(),
+        Some(Backbone {
+            forced: vec![(0, false), (1, false)],
+            ambiguous: vec![],
+            undecided: vec![],
+        })
+    );
+}
+
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn subset(&mut self, n: usize) -> Vec<usize> {
+        (0..n).filter(|_| self.below(2) == 0).collect()
+    }
+}
+
+/// Whether `model` satisfies every constraint, checked directly.
+fn satisfies(problem: &ConstraintProblem, tables: &[Vec<bool>], model: &[bool]) -> bool {
+    problem.cardinalities.iter().all(|c| {
+        let trues = c.vars.iter().filter(|v| model[**v]).count();
+        c.min <= trues && trues <= c.max
+    }) && problem.theories.iter().zip(tables).all(|(t, table)| {
+        let index = t.vars.iter().enumerate().map(|(i, v)| (model[*v] as usize) << i).sum::<usize>();
+        table[index]
+    })
+}
+
+#[test]
+fn agrees_with_brute_force() {
+    for seed in 0..RANDOM_CASES {
+        let mut rng = Rng(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1);
+        let n = 1 + rng.below(7);
+        let mut problem = ConstraintProblem::new(n);
+
+        for _ in 0..rng.below(4) {
+            let vars = rng.subset(n);
+            let min = rng.below(vars.len() + 1);
+            let max = min + rng.below(vars.len() + 1 - min);
+            problem.cardinalities.push(Cardinality { vars, min, max });
+        }
+        let mut tables = vec![];
+        for id in 0..rng.below(4) {
+            let vars = rng.subset(n);
+            let table: Vec<bool> = (0..1 << vars.len()).map(|_| rng.below(4) != 0).collect();
+            let lookup = table.clone();
+            problem.theories.push(theory(&format!("T{id}"), &vars, move |values| {
+                lookup[values.iter().enumerate().map(|(i, v)| (*v as usize) << i).sum::<usize>()]
+            }));
+            tables.push(table);
+        }
+
+        let expected: Vec<Vec<bool>> = (0..1usize << n)
+            .map(|bits| (0..n).map(|v| bits >> (n - 1 - v) & 1 == 1).collect::<Vec<_>>())
+            .filter(|model| satisfies(&problem, &tables, model))
+            .collect();
+        let enumeration = problem.enumerate(usize::MAX);
+        assert!(enumeration.complete, "seed {seed}");
+        assert_eq!(enumeration.models, expected, "seed {seed}");
+
+        let fixed = problem.enumerate_with(&[(0, true)], usize::MAX);
+        let expected_fixed: Vec<_> = expected.iter().filter(|m| m[0]).cloned().collect();
+        assert_eq!(fixed.models, expected_fixed, "seed {seed}");
+
+        let backbone = problem.backbone();
+        if expected.is_empty() {
+            assert_eq!(backbone, None, "seed {seed}");
+            continue;
+        }
+        let constant = |v: usize| expected.iter().all(|m| m[v] == expected[0][v]);
+        assert_eq!(
+            backbone,
+            Some(Backbone {
+                forced: (0..n).filter(|v| constant(*v)).map(|v| (v, expected[0][v])).collect(),
+                ambiguous: (0..n).filter(|v| !constant(*v)).collect(),
+                undecided: vec![],
+            }),
+            "seed {seed}"
+        );
+    }
+}
@@ -0,0 +1,332 @@
+//! Golden tests for the solved logs. The saved logs are replayed through
+//! [`revert_log_and_remake`], and what the issue finders report on every AHSS
+//! stem and EHP diagonal up to `MAX_VERIFY_STEM`, and the convergence of every
+//! sphere (EHP) and RP truncation (AHSS), must match the snapshots in
+//! `logic/golden/`; the AHSS of the Moore spectrum, CP^2 and HP^2 in
+//! `spectra/` must be solved from the facts listed there and then converge to
+//! their Adams E2 data. A change that moves any homotopy group fails here.
+//! Enumerating the stem the EHP log leaves open must find exactly its two
+//! consistent worlds.
+//!
+//! The minimal logs only hold the choices the solvers cannot deduce. They are
+//! run through the solvers as `automated_ahss(true)` and `automated_ehp(true)`
+//! do: the AHSS must find the facts of the full log, the EHP must be free of
+//! issues as far as the full log is. The issue snapshots say how far the logs
+//! are solved: the AHSS to stem 46, the EHP to diagonal 42.
+//!
+//! After an intended change, rewrite the snapshots with
+//! `UPDATE_GOLDEN=1 cargo test` and review the diff.
+
+use std::{fmt::Write as _, fs, path::PathBuf, sync::LazyLock};
+
+use crate::{
+    MAX_STEM, MAX_VERIFY_STEM,
+    data::{
+        curtis::{DATA, MODEL, STABLE_DATA, STABLE_MODEL},
+        cw::CwSpectrum,
+        r#static::synthetic_rp_truncations,
+    },
+    domain::{
+        e1::E1,
+        homotopy::{ComputedGroup, homotopy_group},
+        model::SyntheticSS,
+        process::compute_pages,
+    },
+    io::{export::repo_root_path, import::get_log},
+    solve::{
+        action::{Action, revert_log_and_remake},
+        ahss::{find_ahss_issues, find_cw_issues},
+        automated_ahss::ahss_solver,
+        automated_cw::cw_solver,
+        automated_ehp::ehp_solver,
+        ehp::{apply_ehp_recursively, find_ehp_issues},
+        ehp_ahss::{ehp_to_ahss_map, set_metastable_range},
+        enumerate::enumerate_stem,
+        issues::Issue,
+    },
+    types::{Kind, Torsion},
+};
+
+/// The search settles a choice by whichever world is refuted first. With
+/// fewer threads it can refute a world deep in the search before the other
+/// one at the current stem, and choose differently from the saved logs, so
+/// the solvers run on a pool of this many threads.
+const SOLVER_THREADS: usize = 8;
+
+/// The replayed AHSS, and the replayed EHP on top of it.
+static REPLAYED: LazyLock<(SyntheticSS, SyntheticSS)> = LazyLock::new(|| {
+    let mut ahss_log = get_log(false, true).unwrap();
+    let ahss = revert_log_and_remake(0, &mut ahss_log, &STABLE_MODEL, &STABLE_DATA, true);
+
+    let mut original_data = DATA.clone();
+    set_metastable_range(&mut original_data, &ahss).unwrap();
+    let mut ehp_log = get_log(false, false).unwrap();
+    let ehp = revert_log_and_remake(0, &mut ehp_log, &MODEL, &original_data, false);
+    (ahss, ehp)
+});
+
+fn torsion_name(torsion: Torsion) -> String {
+    match torsion.0 {
+        None => "free".to_string(),
+        Some(n) => format!("tau^{n}"),
+    }
+}
+
+fn on_solver_pool<T: Send>(solve: impl FnOnce() -> T + Send) -> T {
+    rayon::ThreadPoolBuilder::new().num_threads(SOLVER_THREADS).build().unwrap().install(solve)
+}
+
+/// The facts of `log` without their proofs (which list issues in no fixed
+/// order), sorted.
+fn facts(log: &[Action]) -> Vec<String> {
+    let mut facts: Vec<_> = log
+        .iter()
+        .map(|action| {
+            let mut action = action.clone();
+            match &mut action {
+                Action::AddDiff { proof, .. } | Action::AddExt { proof, .. } | Action::AddTwoExt { proof, .. } => {
+                    *proof = None
+                }
+                Action::AddInt { proof, .. } | Action::SetE1 { proof, .. } | Action::SetInducedName { proof, .. } => {
+                    proof.clear()
+                }
+                Action::Revert { .. } => {}
+            }
+            format!("{action:?}")
+        })
+        .collect();
+    facts.sort();
+    facts
+}
+
+/// One line of issues, sorted as the issue finders collect them in no fixed
+/// order.
+fn issue_line(out: &mut String, name: &str, issues: Result<(), Vec<Issue>>) {
+    match issues {
+        Ok(()) => {
+            let _ = writeln!(out, "{name}: ok");
+        }
+        Err(issues) => {
+            let mut issues: Vec<_> = issues.iter().map(|issue| format!("{issue:?}")).collect();
+            issues.sort();
+            let _ = writeln!(out, "{name}: {}", issues.join(", "));
+        }
+    }
+}
+
+/// One line per AHSS stem: the issues found.
+fn ahss_issues(ahss: &SyntheticSS) -> String {
+    let mut out = String::new();
+    for stem in 2..=MAX_VERIFY_STEM {
+        issue_line(&mut out, &format!("stem {stem}"), find_ahss_issues(ahss, &STABLE_MODEL, stem));
+    }
+    out
+}
+
+/// One line per (slanted) EHP diagonal: the issues found.
+fn ehp_issues(ahss: &SyntheticSS, ehp: &SyntheticSS) -> String {
+    let mut ehp = ehp.clone();
+    let map = ehp_to_ahss_map();
+    let mut out = String::new();
+    for diagonal in 2..=MAX_VERIFY_STEM {
+        let issues = find_ehp_issues(&mut ehp, &MODEL, ahss, &STABLE_MODEL, &map, diagonal, true);
+        issue_line(&mut out, &format!("diagonal {diagonal}"), issues);
+    }
+    out
+}
+
+/// One line per stem: the τ-modules every AF converges to.
+fn convergence(out: &mut String, name: &str, data: &SyntheticSS, model: &E1, bot_trunc: i32, top_trunc: i32) {
+    let (pages, _) = compute_pages(data, model, bot_trunc, top_trunc, 0, MAX_VERIFY_STEM, true);
+    for stem in 0..=MAX_VERIFY_STEM {
+        let conv = pages.convergence_at_stem(model, stem);
+        let mut afs: Vec<_> = conv.keys().copied().collect();
+        afs.sort();
+        let _ = write!(out, "{name} stem {stem}:");
+        for af in afs {
+            let modules: Vec<_> = conv[&af].iter().map(|&t| torsion_name(t)).collect();
+            let _ = write!(out, " {af}:[{}]", modules.join(", "));
+        }
+        out.push('\n');
+    }
+}
+
+fn golden_path(file_name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("golden").join(file_name)
+}
+
+/// Compare `actual` against the snapshot `file_name`, or rewrite it if
+/// `UPDATE_GOLDEN` is set.
+fn check_golden(file_name: &str, actual: &str) {
+    let path = golden_path(file_name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Could not read {}: {e} (run with UPDATE_GOLDEN=1 to create it)", path.display()));
+    let changed: Vec<_> = expected
+        .lines()
+        .zip(actual.lines())
+        .filter(|(e, a)| e != a)
+        .take(10)
+        .map(|(e, a)| format!("  expected: {e}\n  got:      {a}"))
+        .collect();
+    assert!(
+        changed.is_empty() && expected.lines().count() == actual.lines().count(),
+        "{} does not match:\n{}",
+        path.display(),
+        changed.join("\n")
+    );
+}
+
+#[test]
+fn ahss_log_issues_match_golden() {
+    let (ahss, _) = &*REPLAYED;
+    check_golden("ahss_issues.txt", &ahss_issues(ahss));
+}
+
+/// `automated_ahss(true)`: the solver finds the saved log from the minimal one.
+#[test]
+fn ahss_solver_reproduces_log() {
+    let (log, ahss) = on_solver_pool(|| ahss_solver(get_log(true, true).ok()));
+    assert_eq!(facts(&log), facts(&get_log(false, true).unwrap()));
+    check_golden("ahss_issues.txt", &ahss_issues(&ahss));
+}
+
+/// Solve the AHSS of the spectrum in `file` from its listed facts, check
+/// every stem with the facts found and return them.
+fn solve_spectrum(file: &str) -> Vec<Action> {
+    let (ahss, _) = &*REPLAYED;
+    let mut spectrum = CwSpectrum::read(&repo_root_path(file)).unwrap();
+    let (model, data) = spectrum.model(ahss).unwrap();
+    let expected = spectrum.adams_e2().unwrap();
+    let (facts, solved) = cw_solver(&spectrum, &model, data, &expected, spectrum.max_stem());
+    assert!(solved, "The solver left facts about {} open: {facts:?}", spectrum.name);
+
+    spectrum.facts.extend(facts.iter().cloned());
+    let (model, data) = spectrum.model(ahss).unwrap();
+    for stem in spectrum.bot_cell()..=spectrum.max_stem() {
+        if let Err(issues) = find_cw_issues(&data, &model, &spectrum, &expected, stem) {
+            panic!("{} stem {stem} has issues: {issues:?}", spectrum.name);
+        }
+    }
+    facts
+}
+
+#[test]
+fn moore_spectrum_is_solved() {
+    let facts = solve_spectrum("spectra/moore.json");
+    // The first tau extension of RP_1^2 is found, not listed.
+    assert!(facts.iter().any(|a| matches!(
+        a,
+        Action::AddExt { from, to, af: 6, kind: Kind::Real, .. } if from == "3 4 4 1 1 1[1]" && to == "12 1 1 1[0]"
+    )));
+}
+
+/// CP^2 and HP^2 against Ext from the resolution, below the first Adams
+/// differential.
+#[test]
+fn projective_planes_are_solved() {
+    solve_spectrum("spectra/cp2.json");
+    solve_spectrum("spectra/hp2.json");
+}
+
+#[test]
+fn ehp_log_issues_match_golden() {
+    let (ahss, ehp) = &*REPLAYED;
+    check_golden("ehp_issues.txt", &ehp_issues(ahss, ehp));
+}
+
+/// `automated_ehp(true)`: the solver, on the replayed AHSS and the minimal
+/// log, solves the EHP as far as the saved log is solved, i.e. up to the first
+/// diagonal of the snapshot with issues. Past that the choices it makes depend
+/// on which world is refuted first, so they are not compared.
+#[test]
+fn ehp_solver_matches_log() {
+    let path = golden_path("ehp_issues.txt");
+    let snapshot = fs::read_to_string(&path).unwrap_or_else(|e| panic!("Could not read {}: {e}", path.display()));
+    let solved: Vec<_> = snapshot.lines().take_while(|line| line.ends_with(": ok")).collect();
+    assert!(!solved.is_empty(), "{} has issues on the first diagonal", path.display());
+
+    let (ahss, _) = &*REPLAYED;
+    let (_, ehp) = on_solver_pool(|| ehp_solver(ahss, get_log(true, false).ok()));
+    let issues = ehp_issues(ahss, &ehp);
+    assert_eq!(issues.lines().take(solved.len()).collect::<Vec<_>>(), solved);
+}
+
+/// The one fact the EHP log leaves `Unknown`, in stem 35, is consistent both
+/// ways, and the two worlds disagree on π_42(S^7) but not on the stable stem.
+#[test]
+fn ehp_stem_35_has_two_worlds() {
+    let (_, ehp) = &*REPLAYED;
+    let mut ehp = ehp.clone();
+    for i in 2..=MAX_STEM {
+        let _ = apply_ehp_recursively(&mut ehp, &MODEL, i, false);
+    }
+    let report = enumerate_stem(&ehp, &MODEL, 35, 16, false);
+    assert!(report.complete);
+    assert_eq!(report.candidates.len(), 1);
+    let assignments: Vec<_> = report.configurations.iter().map(|c| c.assignment.clone()).collect();
+    assert_eq!(assignments, [[false], [true]]);
+    assert!(!report.orders_determined());
+
+    let order = |world: usize, sphere: &str, degree: i32| {
+        report.configurations[world]
+            .orders
+            .iter()
+            .find(|(name, d, _)| name == sphere && *d == degree)
+            .map(|o| o.2)
+    };
+    assert_eq!((order(0, "S^7", 42), order(1, "S^7", 42)), (Some(6), Some(5)));
+    assert_eq!((order(0, "S^6", 41), order(1, "S^6", 41)), (Some(11), Some(11)));
+    assert_eq!((order(0, "S^38", 73), order(1, "S^38", 73)), (Some(5), Some(5)));
+}
+
+#[test]
+fn homotopy_groups_are_derived_or_unknown() {
+    let (_, ehp) = &*REPLAYED;
+    let mut ehp = ehp.clone();
+    for i in 2..=12 {
+        let _ = apply_ehp_recursively(&mut ehp, &MODEL, i, false);
+    }
+    let group = |sphere: i32, stem: i32| {
+        let (pages, _) = compute_pages(&ehp, &MODEL, 0, sphere - 1, 0, 12, true);
+        homotopy_group(&ehp, &MODEL, &pages, sphere, stem)
+    };
+    let known = |s: &str| ComputedGroup::Known(s.parse().unwrap());
+
+    // Stable: h0-towers in stem 3 and 7, and no extension in stem 8.
+    assert_eq!(group(5, 3), known("Z/8"));
+    assert_eq!(group(9, 7), known("Z/16"));
+    assert_eq!(group(10, 8), known("Z/2 ⊕ Z/2"));
+    // Unstable extensions are not derived: π_6(S^3) = Z/4, π_7(S^4) = Z ⊕ Z/4.
+    assert_eq!(group(3, 3), ComputedGroup::UnknownExtension { free: 0, valuation: 2 });
+    assert_eq!(group(4, 3), ComputedGroup::UnknownExtension { free: 1, valuation: 2 });
+}
+
+#[test]
+fn ahss_convergence_matches_golden() {
+    let (ahss, _) = &*REPLAYED;
+    let mut out = String::new();
+    for &(bot_trunc, top_trunc) in synthetic_rp_truncations() {
+        convergence(&mut out, &format!("RP_{bot_trunc}^{top_trunc}"), ahss, &STABLE_MODEL, bot_trunc, top_trunc);
+    }
+    check_golden("ahss_convergence.txt", &out);
+}
+
+#[test]
+fn ehp_convergence_matches_golden() {
+    let (_, ehp) = &*REPLAYED;
+    let mut ehp = ehp.clone();
+    for i in 2..=MAX_STEM {
+        let _ = apply_ehp_recursively(&mut ehp, &MODEL, i, false);
+    }
+    let mut out = String::new();
+    for sphere in 1..=MAX_VERIFY_STEM + 2 {
+        convergence(&mut out, &format!("S^{sphere}"), &ehp, &MODEL, 0, sphere - 1);
+    }
+    check_golden("ehp_convergence.txt", &out);
+}
@@ -0,0 +1,63 @@
+//! The incremental page computation against a from-scratch one: both saved
+//! logs are replayed action by action, and after each action the cached pages
+//! of a few windows must equal freshly computed ones, issues included.
+
+use crate::{
+    MAX_STEM,
+    data::curtis::{DATA, MODEL, STABLE_DATA, STABLE_MODEL},
+    domain::{
+        e1::E1,
+        incremental::{Window, cached_window},
+        model::SyntheticSS,
+        process::compute_window,
+    },
+    io::import::get_log,
+    solve::{
+        action::{Action, process_action, revert_log_and_remake},
+        ehp_ahss::set_metastable_range,
+    },
+};
+
+/// Replay `log` on `data` in the order of [`revert_log_and_remake`], checking
+/// every window after each action.
+fn replay_and_compare(mut data: SyntheticSS, model: &E1, log: &[Action], ahss: bool, windows: &[Window]) {
+    let set_e1 = log.iter().filter(|a| matches!(a, Action::SetE1 { .. }));
+    let rest = log.iter().filter(|a| !matches!(a, Action::SetE1 { .. }));
+    for (i, action) in set_e1.chain(rest).enumerate() {
+        let mut action = action.clone();
+        action.resolve_names(model).unwrap();
+        process_action(&mut data, model, &action, ahss).unwrap();
+
+        for &window in windows {
+            let cached = cached_window(&data, model, window);
+            let fresh = compute_window(&data, model, window);
+            assert!(
+                cached.pages == fresh.pages,
+                "Pages of window {window:?} differ after action {i}: {action:?}"
+            );
+            assert!(
+                cached.issues == fresh.issues,
+                "Issues of window {window:?} differ after action {i}: {action:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn cached_pages_match_fresh_pages_on_ahss_log() {
+    let log = get_log(false, true).unwrap();
+    let windows = [(0, 256, 0, MAX_STEM, true), (1, 8, 0, MAX_STEM, true), (3, 20, 10, 20, false)];
+    replay_and_compare(STABLE_DATA.clone(), &STABLE_MODEL, &log, true, &windows);
+}
+
+#[test]
+fn cached_pages_match_fresh_pages_on_ehp_log() {
+    let mut ahss_log = get_log(false, true).unwrap();
+    let ahss = revert_log_and_remake(0, &mut ahss_log, &STABLE_MODEL, &STABLE_DATA, true);
+    let mut data = DATA.clone();
+    set_metastable_range(&mut data, &ahss).unwrap();
+
+    let log = get_log(false, false).unwrap();
+    let windows = [(0, MAX_STEM, 0, MAX_STEM, true), (0, 6, 0, MAX_STEM, true), (0, 12, 5, 15, false)];
+    replay_and_compare(data, &MODEL, &log, false, &windows);
+}
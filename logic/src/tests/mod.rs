@@ -0,0 +1,28 @@
+//! Regression tests. They run on the saved logs and the Curtis tables, so they
+//! exercise the real computation rather than toy inputs.
+//!
+//! - [`adams_e2`]: parsing the Adams E2 CSVs of Lin's program.
+//! - [`constraint`]: the DPLL solver behind the constraint backend, on small
+//!   hand-built problems and against brute force.
+//! - [`golden`]: replays the saved logs, and solves from the minimal ones,
+//!   and checks them against committed snapshots (`logic/golden/`) of the
+//!   issues found and of what every truncation converges to, and the
+//!   described CW spectra in `spectra/`.
+//! - [`incremental`]: the cached page computation against a from-scratch one
+//!   after every action of both logs.
+//! - [`names`]: the resolver from typed (Ext, Toda, loosely spaced) names to
+//!   E1 generators.
+//! - [`query`]: searching the E1 page by bidegree, tag and name.
+//! - [`resolution`]: the built-in minimal resolution against Lin's CSVs and
+//!   the algebraic AHSS, and the modules of CP and HP.
+//! - [`properties`]: property tests for the page semantics of differentials
+//!   and τ-multiplications on small random instances.
+
+mod adams_e2;
+mod constraint;
+mod golden;
+mod incremental;
+mod names;
+mod properties;
+mod query;
+mod resolution;
@@ -0,0 +1,82 @@
+//! The resolver that turns typed names into canonical E1 generator names
+//! ([`E1::resolve`]), on the EHP model and the shipped `class_names.json`.
+
+use crate::{
+    data::curtis::{MODEL, STABLE_MODEL},
+    domain::e1::{E1, ResolveError},
+    types::Generator,
+};
+
+fn resolved(model: &E1, name: &str) -> Result<String, ResolveError> {
+    model.resolve(name).map(|id| model.name(id).to_string())
+}
+
+#[test]
+fn canonical_names_resolve_to_themselves() {
+    for g in MODEL.gens() {
+        assert_eq!(resolved(&MODEL, &g.name).as_deref(), Ok(g.name.as_str()));
+    }
+    for g in STABLE_MODEL.gens() {
+        assert_eq!(resolved(&STABLE_MODEL, &g.name).as_deref(), Ok(g.name.as_str()));
+    }
+}
+
+#[test]
+fn lambda_word_spellings() {
+    assert_eq!(resolved(&MODEL, " 5  3 [6] ").as_deref(), Ok("5 3[6]"));
+    assert_eq!(resolved(&MODEL, "6 5 3").as_deref(), Ok("5 3[6]"));
+    assert_eq!(resolved(&MODEL, "7").as_deref(), Ok("[7]"));
+}
+
+#[test]
+fn ext_names() {
+    assert_eq!(resolved(&MODEL, "c_0[6]").as_deref(), Ok("2 3 3[6]"));
+    assert_eq!(resolved(&MODEL, "h_1 d_0[5]").as_deref(), Ok("2 4 3 3 3[5]"));
+    assert_eq!(resolved(&MODEL, "h1d0[5]").as_deref(), Ok("2 4 3 3 3[5]"));
+    assert_eq!(resolved(&MODEL, "h₁[3]").as_deref(), Ok("1[3]"));
+    assert_eq!(resolved(&MODEL, "[3]").as_deref(), Ok("[3]"));
+    // A bare Ext name is the class with that leading word.
+    assert_eq!(resolved(&MODEL, "c_0").as_deref(), Ok("3 3[2]"));
+}
+
+#[test]
+fn toda_names() {
+    for name in ["ε", "ε_3", "epsilon", "ε₃"] {
+        assert_eq!(resolved(&MODEL, name).as_deref(), Ok("3 3[2]"), "{name}");
+    }
+    for name in ["σ²", "sigma^2", "σ^2"] {
+        assert_eq!(resolved(&MODEL, name).as_deref(), Ok("7[7]"), "{name}");
+    }
+    assert_eq!(resolved(&MODEL, "nubar").as_deref(), Ok("3[5]"));
+    assert_eq!(resolved(&STABLE_MODEL, "κ").as_deref(), Ok("2 3 3[6]"));
+    // σ lives on S^9 but is born on S^8.
+    assert_eq!(resolved(&MODEL, "σ_9").as_deref(), Ok("[7]"));
+    assert!(matches!(resolved(&MODEL, "σ_5"), Err(ResolveError::Unknown(_))));
+}
+
+#[test]
+fn unknown_names_are_reported() {
+    assert_eq!(resolved(&MODEL, "foo"), Err(ResolveError::Unknown("foo".to_string())));
+    assert!(matches!(resolved(&MODEL, "c_0[100]"), Err(ResolveError::Unknown(_))));
+}
+
+#[test]
+fn ambiguous_names_list_every_candidate() {
+    // The shipped models never have two generators one name can mean, so make
+    // one where the typed name (trimmed) and its Ext spelling are both names.
+    let model = E1::new(vec![
+        Generator::new("h_1[3]".to_string(), 1, 3, 1, 0, None),
+        Generator::new("1[3]".to_string(), 1, 3, 1, 0, None),
+    ]);
+    let error = resolved(&model, " h_1[3] ");
+    let message = error.as_ref().map_err(|e| e.to_string()).unwrap_err();
+    assert!(message.ends_with("h_1[3], 1[3]") || message.ends_with("1[3], h_1[3]"), "{message}");
+    match error {
+        Err(ResolveError::Ambiguous(name, mut candidates)) => {
+            assert_eq!(name, " h_1[3] ");
+            candidates.sort();
+            assert_eq!(candidates, ["1[3]", "h_1[3]"]);
+        }
+        other => panic!("expected an ambiguous name, got {other:?}"),
+    }
+}
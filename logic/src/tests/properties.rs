@@ -0,0 +1,357 @@
+//! Property tests for the page semantics of [`crate::domain::process`]: the
+//! τ-torsion arithmetic of differentials and τ-multiplications, checked on
+//! small randomly generated [`E1`] / [`SyntheticSS`] instances.
+//!
+//! Instances come from a seeded xorshift generator, so every run checks the
+//! same cases and a failure names the seed that reproduces it. Set
+//! `PROPERTY_CASES` to check more cases.
+
+use std::{
+    cell::Cell,
+    collections::HashSet,
+    panic::{AssertUnwindSafe, catch_unwind},
+};
+
+use crate::{
+    domain::{
+        e1::E1,
+        incremental::Fact,
+        model::SyntheticSS,
+        process::{apply_fact, compute_window},
+        ss::{GeneratorState, SSPages},
+    },
+    types::{Generator, Kind, Torsion},
+};
+
+const DEFAULT_CASES: u64 = 2000;
+const BASE_SEED: u64 = 0x5eed_2024;
+
+/// Stems of the random instances are `0..STEMS`.
+const STEMS: i32 = 4;
+const MAX_Y: i32 = 6;
+const MAX_AF: i32 = 8;
+const MAX_TORSION: i32 = 5;
+
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // xorshift has a fixed point at 0.
+        Self(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Uniform in `lo..=hi`.
+    fn range(&mut self, lo: i32, hi: i32) -> i32 {
+        lo + (self.next() % (hi - lo + 1) as u64) as i32
+    }
+
+    fn chance(&mut self, one_in: u64) -> bool {
+        self.next().is_multiple_of(one_in)
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.next() as usize % items.len()]
+    }
+
+    /// Reorder `facts` by swapping random neighbours whose endpoints are
+    /// disjoint, so facts sharing a generator keep their relative order.
+    fn commute<T>(&mut self, facts: &mut [T], ends: impl Fn(&T) -> (usize, usize)) {
+        if facts.len() < 2 {
+            return;
+        }
+        for _ in 0..facts.len() * facts.len() {
+            let i = self.range(0, facts.len() as i32 - 2) as usize;
+            let ((a, b), (c, d)) = (ends(&facts[i]), ends(&facts[i + 1]));
+            if a != c && a != d && b != c && b != d {
+                facts.swap(i, i + 1);
+            }
+        }
+    }
+
+    /// τ-free a third of the time, otherwise τ^n-torsion (possibly dead).
+    fn torsion(&mut self) -> Torsion {
+        if self.chance(3) {
+            Torsion::default()
+        } else {
+            Torsion::new(self.range(0, MAX_TORSION))
+        }
+    }
+}
+
+fn cases() -> u64 {
+    std::env::var("PROPERTY_CASES")
+        .ok()
+        .and_then(|c| c.parse().ok())
+        .unwrap_or(DEFAULT_CASES)
+}
+
+/// Run `property` on `PROPERTY_CASES` seeds, reporting the seed that fails.
+fn check(name: &str, property: impl Fn(&mut Rng)) {
+    for case in 0..cases() {
+        let seed = BASE_SEED + case;
+        if catch_unwind(AssertUnwindSafe(|| property(&mut Rng::new(seed)))).is_err() {
+            panic!("{name} fails for seed {seed}");
+        }
+    }
+}
+
+fn generator(i: usize, stem: i32, y: i32, af: i32) -> Generator {
+    Generator::new(format!("g{i}"), stem, y, af, 0, None)
+}
+
+/// A model of two generators `0 -> 1`, with `data` asserting the fact between
+/// them, and pages holding the given states on page 1.
+fn two_generators(
+    rng: &mut Rng,
+    from: (i32, i32, GeneratorState),
+    to: (i32, i32, GeneratorState),
+    fact: Fact,
+) -> (E1, SyntheticSS, SSPages) {
+    let ((from_stem, from_y, from_state), (to_stem, to_y, to_state)) = (from, to);
+    let model = E1::new(vec![
+        generator(0, from_stem, from_y, from_state.0),
+        generator(1, to_stem, to_y, to_state.0),
+    ]);
+    let mut data = SyntheticSS::empty(model.clone());
+    let kind = rng.pick(&[Kind::Real, Kind::Algebraic]);
+    match fact {
+        Fact::Diff { .. } => data.add_diff(&model, 0, 1, None, kind),
+        Fact::IntTau { page, .. } => data.add_int_tau(0, 1, page, None, Kind::Real),
+        Fact::ExtTau { af, .. } => data.add_ext_tau(&model, 0, 1, af, None, Kind::Real),
+    }
+    let pages = SSPages {
+        bot_trunc: 0,
+        top_trunc: MAX_Y,
+        generators: vec![Some(vec![(1, from_state)]), Some(vec![(1, to_state)])],
+    };
+    (model, data, pages)
+}
+
+/// A random model with a few generators in every stem, random E1 torsion, and
+/// random differentials and internal / external τ-multiplications.
+fn random_instance(rng: &mut Rng) -> (E1, SyntheticSS) {
+    let mut gens = vec![];
+    for stem in 0..STEMS {
+        for _ in 0..rng.range(1, 5) {
+            gens.push(generator(gens.len(), stem, rng.range(0, MAX_Y), rng.range(0, MAX_AF)));
+        }
+    }
+    let model = E1::new(gens);
+    let mut data = SyntheticSS::empty(model.clone());
+    for id in 0..model.gens().len() {
+        data.set_torsion(id, rng.torsion());
+    }
+
+    for _ in 0..rng.range(0, 12) {
+        let stem = rng.range(1, STEMS - 1);
+        let from = rng.pick(model.gens_id_in_stem(stem));
+        let to = rng.pick(model.gens_id_in_stem(stem - 1));
+        if model.y(from) > model.y(to) {
+            data.add_diff(&model, from, to, None, rng.pick(&[Kind::Real, Kind::Algebraic]));
+        }
+    }
+    for _ in 0..rng.range(0, 4) {
+        let ids = model.gens_id_in_stem(rng.range(0, STEMS - 1));
+        let (from, to) = (rng.pick(ids), rng.pick(ids));
+        if from == to {
+            continue;
+        }
+        if rng.chance(2) {
+            data.add_int_tau(from, to, rng.range(1, MAX_Y), None, Kind::Real);
+        } else {
+            data.add_ext_tau(&model, from, to, rng.range(0, MAX_AF), None, Kind::Real);
+        }
+    }
+    (model, data)
+}
+
+/// The final state of every generator, and the issues raised, of the full
+/// computation of `data`.
+fn compute(data: &SyntheticSS, model: &E1) -> (Vec<Option<GeneratorState>>, Vec<String>) {
+    let window = compute_window(data, model, (0, MAX_Y, 0, STEMS - 1, true));
+    let finals = (0..model.gens().len()).map(|id| window.pages.try_element_final(id)).collect();
+    let mut issues: Vec<_> = window.issues.iter().map(|(_, _, i)| format!("{i:?}")).collect();
+    issues.sort();
+    (finals, issues)
+}
+
+#[test]
+fn torsion_order_agrees_with_can_map() {
+    check("torsion_order_agrees_with_can_map", |rng| {
+        let (a, b, c) = (rng.torsion(), rng.torsion(), rng.torsion());
+        assert_eq!(a <= b, b.can_map_with_coeff(&a, 0), "{a:?} <= {b:?}");
+        assert!(a <= b || b <= a);
+        assert_eq!(a <= b && b <= a, a == b);
+        if a <= b && b <= c {
+            assert!(a <= c);
+        }
+        // A larger coefficient can only make more maps possible.
+        let coeff = rng.range(0, MAX_TORSION);
+        if b.can_map_with_coeff(&a, coeff) {
+            assert!(b.can_map_with_coeff(&a, coeff + 1));
+        }
+    });
+}
+
+/// A differential hits as many F2s as it kills: a torsion source and target
+/// lose the same amount, a free source keeps the bottom of its tower, and a
+/// free source kills a free target.
+#[test]
+fn differential_conserves_rank() {
+    // The cases where the differential acts, which should be a fair share.
+    let acting = Cell::new(0);
+    check("differential_conserves_rank", |rng| {
+        // Mostly targets a little above the source, where differentials do something.
+        let from = (rng.range(0, MAX_AF), rng.torsion());
+        let to = (from.0 + rng.range(0, 3), rng.torsion());
+        let page = rng.range(1, MAX_Y);
+        let fact = Fact::Diff { page, from: 0, to: 1 };
+        let (model, data, mut pages) = two_generators(rng, (1, page, from), (0, 0, to), fact);
+
+        if apply_fact(&data, &model, &mut pages, fact).is_err() {
+            return;
+        }
+        let (new_from, new_to) = (pages.element_final(0), pages.element_final(1));
+        if !from.1.alive() || !to.1.alive() {
+            assert_eq!((new_from, new_to), (from, to));
+            return;
+        }
+        if (new_from, new_to) == (from, to) {
+            return;
+        }
+        acting.set(acting.get() + 1);
+
+        assert_eq!(new_to.0, to.0, "the target's AF changed");
+        let coeff = to.0 - from.0 - 1;
+        assert_eq!(new_to.1, Torsion::new(coeff));
+        match (from.1.0, to.1.0) {
+            (Some(f), Some(t)) => {
+                let (nf, nt) = (new_from.1.0.unwrap(), new_to.1.0.unwrap());
+                assert_eq!(f - nf, t - nt, "{from:?} -> {to:?} gave {new_from:?} -> {new_to:?}");
+                assert_eq!(from.0 - f, new_from.0 - nf, "the bottom of the source moved");
+            }
+            (None, Some(t)) => {
+                assert!(new_from.1.free());
+                assert_eq!(from.0 - new_from.0, t - coeff);
+            }
+            (None, None) => assert_eq!(new_from, (from.0, Torsion::zero())),
+            (Some(_), None) => panic!("a torsion class killed a free class"),
+        }
+    });
+    assert!(acting.get() >= cases() / 4, "only {} of {} differentials acted", acting.get(), cases());
+}
+
+/// A τ-multiplication moves τ-towers around without creating or destroying
+/// any: the total torsion and the bottoms of the towers are kept.
+#[test]
+fn tau_conserves_rank() {
+    check("tau_conserves_rank", |rng| {
+        let from = (rng.range(0, MAX_AF), rng.torsion());
+        let to = (from.0 - rng.range(0, 3), rng.torsion());
+        let fact = if rng.chance(2) {
+            Fact::IntTau { page: rng.range(1, MAX_Y), from: 0, to: 1 }
+        } else {
+            Fact::ExtTau { af: rng.range(0, MAX_AF), from: 0, to: 1 }
+        };
+        let (model, data, mut pages) = two_generators(rng, (0, 1, from), (0, 0, to), fact);
+
+        if apply_fact(&data, &model, &mut pages, fact).is_err() {
+            return;
+        }
+        let (new_from, new_to) = (pages.element_final(0), pages.element_final(1));
+        if (new_from, new_to) == (from, to) {
+            return;
+        }
+
+        assert_eq!((new_from.0, new_to.0), (from.0, to.0), "a τ-multiplication changed an AF");
+        match (from.1.0, to.1.0) {
+            (Some(f), Some(t)) => {
+                let (nf, nt) = (new_from.1.0.unwrap(), new_to.1.0.unwrap());
+                assert_eq!(f + t, nf + nt);
+                let bottoms: HashSet<_> = [from.0 - f, to.0 - t].into();
+                let new_bottoms: HashSet<_> = [new_from.0 - nf, new_to.0 - nt].into();
+                assert_eq!(bottoms, new_bottoms);
+            }
+            (Some(_), None) => assert!(new_from.1.free() && !new_to.1.free()),
+            (None, _) => panic!("a free source was changed by a τ-multiplication"),
+        }
+    });
+}
+
+/// However the facts combine, no state ends up with negative torsion, and each
+/// generator's states are recorded in page order.
+#[test]
+fn torsion_never_negative() {
+    check("torsion_never_negative", |rng| {
+        let (model, data) = random_instance(rng);
+        let window = compute_window(&data, &model, (0, MAX_Y, 0, STEMS - 1, true));
+        for states in window.pages.generators.iter().flatten() {
+            for (page, (_, torsion)) in states {
+                assert!(torsion.0.is_none_or(|t| t >= 0), "{states:?}");
+                assert!(*page >= 1);
+            }
+            assert!(states.windows(2).all(|w| w[0].0 <= w[1].0), "{states:?}");
+        }
+    });
+}
+
+/// Facts of a single page that share no generator commute, so reordering them
+/// never changes the result. This includes pages where a generator takes part
+/// in several facts; there only the order of the facts sharing a generator is
+/// part of the data.
+#[test]
+fn page_order_does_not_matter() {
+    // The cases with a generator in two facts of a page, which should be common.
+    let overlapping = Cell::new(0);
+    check("page_order_does_not_matter", |rng| {
+        let (model, data) = random_instance(rng);
+
+        let mut diffs = data.diffs_page().to_vec();
+        for facts in diffs.iter_mut() {
+            rng.commute(facts, |d| (d.from, d.to));
+        }
+        let mut int_taus = data.internal_tau_page().to_vec();
+        for facts in int_taus.iter_mut() {
+            rng.commute(facts, |t| (t.from, t.to));
+        }
+
+        // Assert the same facts again, each page in the shuffled order.
+        let mut shuffled = SyntheticSS::empty(model.clone());
+        for (id, &torsion) in data.generators().iter().enumerate() {
+            shuffled.set_torsion(id, torsion);
+        }
+        for (page, facts) in int_taus.iter().enumerate() {
+            for t in facts {
+                shuffled.add_int_tau(t.from, t.to, page as i32, None, Kind::Real);
+            }
+        }
+        for d in diffs.iter().flatten() {
+            shuffled.add_diff(&model, d.from, d.to, None, data.facts()[&(d.from, d.to)].0);
+        }
+        for t in data.ext_taus() {
+            shuffled.add_ext_tau(&model, t.from, t.to, t.af, None, Kind::Real);
+        }
+
+        let disjoint = |ends: Vec<(usize, usize)>| {
+            let mut seen = HashSet::new();
+            ends.iter().all(|&(f, t)| seen.insert(f) && seen.insert(t))
+        };
+        let independent = data.diffs_page().iter().all(|ds| disjoint(ds.iter().map(|d| (d.from, d.to)).collect()))
+            && data
+                .internal_tau_page()
+                .iter()
+                .all(|ts| disjoint(ts.iter().map(|t| (t.from, t.to)).collect()));
+        if !independent {
+            overlapping.set(overlapping.get() + 1);
+        }
+        assert_eq!(compute(&data, &model), compute(&shuffled, &model));
+    });
+    assert!(overlapping.get() >= cases() / 10, "only {} of {} cases overlap", overlapping.get(), cases());
+}
@@ -0,0 +1,47 @@
+//! Searching the E1 page ([`search`]) with parsed filters.
+
+use crate::{
+    data::curtis::MODEL,
+    domain::query::{GeneratorQuery, search},
+};
+
+fn names(query: &str) -> Vec<String> {
+    let query = GeneratorQuery::parse(query).unwrap();
+    search(&MODEL, &query).iter().map(|&id| MODEL.name(id).to_string()).collect()
+}
+
+#[test]
+fn filters_narrow_down_a_bidegree() {
+    let all = names("stem=14, af=4");
+    assert!(all.contains(&"2 3 3[6]".to_string()));
+    assert!(all.iter().all(|n| MODEL.get_name(n).stem == 14 && MODEL.get_name(n).af == 4));
+
+    assert_eq!(names("stem=14, af=4, suffix=2 3 3"), ["2 3 3[6]"]);
+    assert_eq!(names("stem=14, y=6, prefix=2"), ["2 3 3[6]"]);
+}
+
+#[test]
+fn exact_names_rank_first() {
+    assert_eq!(names("name=κ").first().map(String::as_str), Some("2 3 3[6]"));
+    assert_eq!(names("name=c_0[6]").first().map(String::as_str), Some("2 3 3[6]"));
+}
+
+#[test]
+fn ranges_and_bad_filters() {
+    let q = GeneratorQuery::parse("born=3..5, dies=never").unwrap();
+    assert_eq!(q.born, Some(3..=4));
+    assert_eq!(q.dies, Some(None));
+    assert!(search(&MODEL, &q).iter().all(|&id| (3..5).contains(&MODEL.get(id).born) && MODEL.get(id).dies.is_none()));
+    assert_eq!(GeneratorQuery::parse("born=3..=5").unwrap().born, Some(3..=5));
+    assert_eq!(GeneratorQuery::parse("dies=..4").unwrap().dies, Some(Some(i32::MIN..=3)));
+    assert_eq!(GeneratorQuery::parse("dies=7..").unwrap().dies, Some(Some(7..=i32::MAX)));
+    assert_eq!(GeneratorQuery::parse("born=6").unwrap().born, Some(6..=6));
+
+    assert!(GeneratorQuery::parse("born=").is_err());
+    assert!(GeneratorQuery::parse("dies=").is_err());
+    assert!(GeneratorQuery::parse("born=3..=").is_err());
+
+    assert!(GeneratorQuery::parse("stem=x").is_err());
+    assert!(GeneratorQuery::parse("colour=red").is_err());
+    assert!(names("stem=200").is_empty());
+}
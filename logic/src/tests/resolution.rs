@@ -0,0 +1,143 @@
+//! The built-in minimal resolution: products in the Milnor basis, Ext against
+//! Lin's CSVs and the algebraic AHSS, CP and HP, and the τ-Bockstein and
+//! h_i-linearity checks of `S0`.
+
+use std::collections::HashMap;
+
+use crate::{
+    data::{
+        classical::ClassicalTable,
+        cw::CwSpectrum,
+        resolution::Resolution,
+        r#static::{S0, S0_ZEROES, algebraic_rp, resolved_compare_data},
+        steenrod::{MilnorBasis, ProjectiveModule, multiply},
+    },
+    io::export::repo_root_path,
+    solve::{
+        issues::Issue,
+        tau_bockstein::{find_tau_bockstein_issues, find_tau_linearity_issues},
+    },
+    types::Torsion,
+};
+
+#[test]
+fn milnor_products() {
+    assert!(multiply(&[1], &[1]).is_empty());
+    assert_eq!(multiply(&[2], &[2]), [vec![1, 1]]);
+    assert_eq!(multiply(&[1], &[2]), [vec![3]]);
+    assert_eq!(multiply(&[2], &[1]), [vec![0, 1], vec![3]]);
+    assert_eq!(multiply(&[], &[0, 1]), [vec![0, 1]]);
+
+    let basis = MilnorBasis::new(7);
+    let dims: Vec<_> = (0..=7).map(|n| basis.in_degree(n).len()).collect();
+    assert_eq!(dims, [1, 1, 1, 2, 2, 2, 3, 4]);
+}
+
+#[test]
+fn h_products_of_the_sphere() {
+    let resolution = Resolution::new(ProjectiveModule::rp(0, 0), 8, 5);
+    let nonzero = |i, stem, s| resolution.h_product(i, stem, s).unwrap().iter().any(|p| !p.is_empty());
+    // h0 on the tower, and h0 h2 ≠ 0 = h0^3 h2, h0 h1 = 0.
+    assert!(nonzero(0, 0, 0));
+    assert!(nonzero(0, 3, 1));
+    assert!(nonzero(0, 3, 2));
+    assert!(!nonzero(0, 3, 3));
+    assert!(!nonzero(0, 1, 1));
+    // h1^3 = h0^2 h2, h1 h2 = 0, h2^2 ≠ 0 = h0 h2^2.
+    assert!(nonzero(1, 2, 2));
+    assert!(!nonzero(1, 3, 1));
+    assert!(nonzero(2, 3, 1));
+    assert!(!nonzero(0, 6, 2));
+    assert_eq!(resolution.h_product(0, 3, 5), None);
+}
+
+#[test]
+fn sphere_agrees_with_lin() {
+    let resolved = resolved_compare_data(0, 0, 20, 10).unwrap();
+    let lin: HashMap<_, _> = S0.iter().filter(|&(&(stem, s), _)| stem <= 20 && s <= 10).collect();
+    assert_eq!(resolved.len(), lin.len());
+    for (key, torsions) in lin {
+        assert_eq!(resolved.get(key), Some(torsions), "stem {}, s {}", key.0, key.1);
+    }
+}
+
+/// Without a CSV the data is τ-free and counts what the algebraic AHSS does.
+#[test]
+fn truncation_without_csv_agrees_with_algebraic_ahss() {
+    let resolution = Resolution::new(ProjectiveModule::rp(4, 6), 20, 12);
+    let resolved = resolution.compare_data(true, None, &[4]).unwrap();
+    let algebraic: HashMap<_, _> =
+        algebraic_rp(4, 6).iter().filter(|&(&(stem, af), _)| stem <= 20 && af <= 13).map(|(&k, &n)| (k, n)).collect();
+    let counts: HashMap<_, _> = resolved
+        .iter()
+        .inspect(|(_, torsions)| assert!(torsions.iter().all(|t| *t == Default::default())))
+        .map(|(&k, torsions)| (k, torsions.len()))
+        .collect();
+    assert_eq!(counts, algebraic);
+}
+
+#[test]
+fn sphere_tau_bockstein() {
+    let table = ClassicalTable::read(&ClassicalTable::default_path()).unwrap();
+    let ext = Resolution::new(ProjectiveModule::rp(0, 0), 20, 10).ext();
+    for stem in 0..=20 {
+        assert_eq!(find_tau_bockstein_issues(&S0_ZEROES, &ext, &table, 10, stem), Ok(()));
+    }
+
+    // h_0^2 h_2 made τ-torsion: still a class of Ext, but π_3 would be Z/4.
+    let mut data = S0_ZEROES.clone();
+    data.get_mut(&(3, 3)).unwrap()[0] = Torsion::new(1);
+    assert_eq!(
+        find_tau_bockstein_issues(&data, &ext, &table, 10, 3),
+        Err(vec![Issue::TauInversion { stem: 3, expected: 3, observed: 2 }])
+    );
+    data.remove(&(3, 3));
+    let issues = find_tau_bockstein_issues(&data, &ext, &table, 10, 3).unwrap_err();
+    assert!(issues.contains(&Issue::TauReduction { stem: 3, af: 3, expected: 1, observed: 0 }));
+}
+
+/// Sq^2 y = y^2 on CP and Sq^4 z = z^2 on HP, nothing odd acts on either, and
+/// a two cell space gets the h_i-multiplications of its attaching map.
+#[test]
+fn complex_and_quaternionic_projective_spaces() {
+    let cp = ProjectiveModule { d: 2, bot: 2, top: 6 };
+    assert!(cp.acts(&[2], 2));
+    assert!(!cp.acts(&[1], 2));
+    assert!(!cp.acts(&[2], 4));
+    assert!(!cp.acts(&[4], 2));
+    let hp = ProjectiveModule { d: 4, bot: 4, top: 12 };
+    assert!(hp.acts(&[4], 4));
+    assert!(!hp.acts(&[2], 4));
+    assert!(!hp.acts(&[4], 8));
+    assert!(!hp.acts(&[8], 4));
+
+    let spectrum = CwSpectrum::read(&repo_root_path("spectra/cp2.json")).unwrap();
+    let (differentials, dropped) = spectrum.algebraic().unwrap();
+    assert!(differentials.iter().any(|d| d.from == "[4]" && d.to == "1[2]"));
+    assert!(dropped.is_empty());
+    let spectrum = CwSpectrum::read(&repo_root_path("spectra/hp2.json")).unwrap();
+    let (differentials, dropped) = spectrum.algebraic().unwrap();
+    assert!(differentials.iter().any(|d| d.from == "[8]" && d.to == "3[4]"));
+    assert_eq!(dropped.len(), 2);
+}
+
+#[test]
+fn sphere_tau_linearity() {
+    let resolution = Resolution::new(ProjectiveModule::rp(0, 0), 20, 10);
+    let ext = resolution.ext();
+    for stem in 0..=20 {
+        assert_eq!(find_tau_linearity_issues(&S0_ZEROES, &resolution, &ext, 10, stem), Ok(()));
+    }
+
+    // h_0 h_2 made τ-torsion while h_0^2 h_2 = h_0 (h_0 h_2) stays τ-free.
+    let mut data = S0_ZEROES.clone();
+    data.get_mut(&(3, 2)).unwrap()[0] = Torsion::new(1);
+    assert_eq!(
+        find_tau_linearity_issues(&data, &resolution, &ext, 10, 3),
+        Err(vec![Issue::TauLinearity { stem: 3, af: 2, i: 0, expected: Torsion::new(1), observed: Torsion::default() }])
+    );
+    // h_1^3 = h_0^2 h_2 made the source of a differential, but h_1^2 is not.
+    data.get_mut(&(3, 3)).unwrap()[0] = Torsion::zero();
+    let issues = find_tau_linearity_issues(&data, &resolution, &ext, 10, 2).unwrap_err();
+    assert_eq!(issues, [Issue::TauLinearity { stem: 2, af: 2, i: 1, expected: Torsion::default(), observed: Torsion::zero() }]);
+}
@@ -99,6 +99,13 @@ pub struct Generator {
     pub dies: Option<i32>,
 
     pub induced_name: Vec<(i32, String)>,
+
+    /// Ext name (e.g. `c_0[6]`) and Toda name of the element it detects, see
+    /// [`crate::data::names`]. Only filled in on export.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alg_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hom_name: Option<String>,
 }
 
 impl Generator {
@@ -119,6 +126,8 @@ impl Generator {
             born,
             dies,
             induced_name: vec![(0, name)],
+            alg_name: None,
+            hom_name: None,
         }
     }
 
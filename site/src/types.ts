@@ -42,7 +42,19 @@ export type ExternalTauMult = {
     "proof"?: string,
 }
 
+export type GeneratorState = [number, [number, number | null]];
+
+export type TruncationPages = {
+    "bot_trunc": number,
+    "top_trunc": number,
+    "generators": [number, GeneratorState[]][],
+}
+
+// Mirrors schema/synthetic_ss.schema.json
 export type SyntheticEHP = {
+    "schema_version"?: number,
+    "max_stem"?: number,
+    "truncations"?: TruncationPages[],
     "generators": Generators[],
     "differentials": Differential[],
     "multiplications": Multiplication[],